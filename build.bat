@@ -1,2 +1,2 @@
-rustdoc --crate-name bag src/bag.rs -o target/doc -L dependency=target/debug/deps
+rustdoc --crate-name bag src/lib.rs -o target/doc -L dependency=target/debug/deps
 cargo run
\ No newline at end of file
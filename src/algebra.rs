@@ -0,0 +1,441 @@
+//! Multiset algebra on the `Bag` type.
+//! 
+//! Every operation counts the elements of one operand into a HashMap first, so each
+//! runs in time linear in the combined size of both bags.
+//! 
+//! Element types that are ordered but not hashable, such as `f64`, have the same
+//! operations with an `ordered_` prefix. These sort both bags and merge their runs of
+//! equal elements, so they run in O(n log n) time. As with `==`, elements that cannot be
+//! compared with themselves, such as NaN, never match an element of the other bag.
+//! 
+//! The bags returned keep the growth policy and shrink threshold of `self`.
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use Bag;
+
+/// Returns the number of times each distinct element occurs in `data`.
+fn count_elements<T: Hash + Eq>(data: &[T]) -> HashMap<&T, u64> {
+	let mut counts = HashMap::new();
+
+	for datum in data {
+		*counts.entry(datum).or_insert(0) += 1;
+	}
+
+	counts
+}
+
+/// Pairs the runs of equal elements of two bags, returning each distinct element with the
+/// number of times it occurs in `left` and in `right`, in ascending order.
+fn paired_runs<'a, T: PartialOrd>(left: &'a Bag<T>, right: &'a Bag<T>) -> Vec<(&'a T, u64, u64)> {
+	let unordered = |value: &T| value.partial_cmp(value).is_none();
+	let left = left.sorted_runs();
+	let right = right.sorted_runs();
+	let mut paired = Vec::with_capacity(left.len() + right.len());
+	let (mut i, mut j) = (0, 0);
+
+	loop {
+		let order = match (left.get(i), right.get(j)) {
+			(None, None) => break,
+			(Some(_), None) => Ordering::Less,
+			(None, Some(_)) => Ordering::Greater,
+			(Some(&(a, _)), Some(&(b, _))) => match a.partial_cmp(b) {
+				Some(order) => order,
+				None if unordered(a) && !unordered(b) => Ordering::Greater,
+				None => Ordering::Less,
+			},
+		};
+
+		match order {
+			Ordering::Less => {
+				paired.push((left[i].0, left[i].1, 0));
+				i += 1;
+			}
+			Ordering::Greater => {
+				paired.push((right[j].0, 0, right[j].1));
+				j += 1;
+			}
+			Ordering::Equal => {
+				paired.push((left[i].0, left[i].1, right[j].1));
+				i += 1;
+				j += 1;
+			}
+		}
+	}
+
+	paired
+}
+
+impl<'a, T: Hash + Eq + Clone> BitOr<&'a Bag<T>> for &'a Bag<T> {
+	type Output = Bag<T>;
+
+	/// Returns the union of two `bag` data structures.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// assert_eq!(x.union(&y), &x | &y);
+	/// ```
+	fn bitor(self, other: &'a Bag<T>) -> Bag<T> {
+		self.union(other)
+	}
+}
+
+impl<'a, T: Hash + Eq + Clone> BitAnd<&'a Bag<T>> for &'a Bag<T> {
+	type Output = Bag<T>;
+
+	/// Returns the intersection of two `bag` data structures.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// assert_eq!(x.intersection(&y), &x & &y);
+	/// ```
+	fn bitand(self, other: &'a Bag<T>) -> Bag<T> {
+		self.intersection(other)
+	}
+}
+
+impl<'a, T: Hash + Eq + Clone> Sub<&'a Bag<T>> for &'a Bag<T> {
+	type Output = Bag<T>;
+
+	/// Returns the difference of two `bag` data structures.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// assert_eq!(x.difference(&y), &x - &y);
+	/// ```
+	fn sub(self, other: &'a Bag<T>) -> Bag<T> {
+		self.difference(other)
+	}
+}
+
+impl<'a, T: Hash + Eq + Clone> BitXor<&'a Bag<T>> for &'a Bag<T> {
+	type Output = Bag<T>;
+
+	/// Returns the symmetric difference of two `bag` data structures.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// assert_eq!(x.symmetric_difference(&y), &x ^ &y);
+	/// ```
+	fn bitxor(self, other: &'a Bag<T>) -> Bag<T> {
+		self.symmetric_difference(other)
+	}
+}
+
+impl<T: Hash + Eq + Clone> Bag<T> {
+	/// Returns a new `bag` in which each element occurs as many times as it does
+	/// in whichever of the two bags holds more copies of it.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// let z: Bag<u32> = x.union(&y);
+	/// assert_eq!(4, z.size());
+	/// assert_eq!(2, z.occurrences(&1));
+	/// assert_eq!(1, z.occurrences(&3));
+	/// ```
+	pub fn union(&self, other: &Bag<T>) -> Bag<T> {
+		let mut remaining = count_elements(&self.data);
+		let mut result = self.clone();
+
+		for datum in &other.data {
+			match remaining.get_mut(datum) {
+				Some(count) if *count > 0 => *count -= 1,
+				_ => result.insert(datum.clone()),
+			}
+		}
+
+		result
+	}
+
+	/// Returns a new `bag` in which each element occurs as many times as it does
+	/// in whichever of the two bags holds fewer copies of it.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, FixedIncrement};
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// let z: Bag<u32> = x.intersection(&y);
+	/// assert_eq!(1, z.size());
+	/// assert_eq!(1, z.occurrences(&1));
+	/// 
+	/// let mut w: Bag<u32> = vec![1, 2].into_iter().collect();
+	/// w.set_growth_policy(FixedIncrement(10));
+	/// let mut v: Bag<u32> = w.intersection(&w);
+	/// v.insert(3);
+	/// v.insert(4);
+	/// assert_eq!(11, v.get_capacity());
+	/// ```
+	pub fn intersection(&self, other: &Bag<T>) -> Bag<T> {
+		let mut remaining = count_elements(&other.data);
+		let mut result = self.empty_like(1);
+
+		for datum in &self.data {
+			if let Some(count) = remaining.get_mut(datum) {
+				if *count > 0 {
+					*count -= 1;
+					result.insert(datum.clone());
+				}
+			}
+		}
+
+		result
+	}
+
+	/// Returns a new `bag` in which each element occurs as many times as it does in this bag
+	/// minus the number of times it occurs in `other`, or not at all if that would be negative.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// let z: Bag<u32> = x.difference(&y);
+	/// assert_eq!(2, z.size());
+	/// assert_eq!(1, z.occurrences(&1));
+	/// assert_eq!(1, z.occurrences(&2));
+	/// ```
+	pub fn difference(&self, other: &Bag<T>) -> Bag<T> {
+		let mut remaining = count_elements(&other.data);
+		let mut result = self.empty_like(1);
+
+		for datum in &self.data {
+			match remaining.get_mut(datum) {
+				Some(count) if *count > 0 => *count -= 1,
+				_ => result.insert(datum.clone()),
+			}
+		}
+
+		result
+	}
+
+	/// Returns a new `bag` in which each element occurs as many times as the difference
+	/// between its multiplicities in the two bags.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 3].into_iter().collect();
+	/// let z: Bag<u32> = x.symmetric_difference(&y);
+	/// assert_eq!(3, z.size());
+	/// assert_eq!(1, z.occurrences(&1));
+	/// assert_eq!(1, z.occurrences(&2));
+	/// assert_eq!(1, z.occurrences(&3));
+	/// ```
+	pub fn symmetric_difference(&self, other: &Bag<T>) -> Bag<T> {
+		self.difference(other) + other.difference(self)
+	}
+
+	/// Returns true if no element occurs more often in this bag than in `other`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 1, 2, 3].into_iter().collect();
+	/// assert!(x.is_subbag_of(&y));
+	/// assert!(!y.is_subbag_of(&x));
+	/// ```
+	pub fn is_subbag_of(&self, other: &Bag<T>) -> bool {
+		if self.size() > other.size() { return false }
+
+		let mut remaining = count_elements(&other.data);
+
+		for datum in &self.data {
+			match remaining.get_mut(datum) {
+				Some(count) if *count > 0 => *count -= 1,
+				_ => return false,
+			}
+		}
+
+		true
+	}
+
+	/// Returns true if no element occurs more often in `other` than in this bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 1, 2, 3].into_iter().collect();
+	/// let y: Bag<u32> = vec![1, 2].into_iter().collect();
+	/// assert!(x.is_superbag_of(&y));
+	/// ```
+	pub fn is_superbag_of(&self, other: &Bag<T>) -> bool {
+		other.is_subbag_of(self)
+	}
+
+	/// Returns true if the two bags have no element in common.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![1, 2].into_iter().collect();
+	/// let y: Bag<u32> = vec![3, 3].into_iter().collect();
+	/// assert!(x.is_disjoint(&y));
+	/// assert!(!x.is_disjoint(&x));
+	/// ```
+	pub fn is_disjoint(&self, other: &Bag<T>) -> bool {
+		let present: HashSet<&T> = other.data.iter().collect();
+
+		!self.data.iter().any(|datum| present.contains(datum))
+	}
+}
+
+impl<T: PartialOrd + Clone> Bag<T> {
+	/// Returns the union of two `bag` data structures, as `union` does, for element types
+	/// that are ordered rather than hashable. The result holds its elements in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 1.5, 2.0].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 3.0].into_iter().collect();
+	/// let z: Bag<f64> = x.ordered_union(&y);
+	/// assert_eq!(&[1.5, 1.5, 2.0, 3.0], &z.get_data()[..]);
+	/// ```
+	pub fn ordered_union(&self, other: &Bag<T>) -> Bag<T> {
+		self.merged(other, |left, right| if left > right { left } else { right })
+	}
+
+	/// Returns the intersection of two `bag` data structures, as `intersection` does, for element
+	/// types that are ordered rather than hashable. The result holds its elements in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 1.5, 2.0, f64::NAN].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 3.0, f64::NAN].into_iter().collect();
+	/// let z: Bag<f64> = x.ordered_intersection(&y);
+	/// assert_eq!(&[1.5], &z.get_data()[..]);
+	/// ```
+	pub fn ordered_intersection(&self, other: &Bag<T>) -> Bag<T> {
+		self.merged(other, |left, right| if left < right { left } else { right })
+	}
+
+	/// Returns the difference of two `bag` data structures, as `difference` does, for element
+	/// types that are ordered rather than hashable. The result holds its elements in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 1.5, 2.0].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 3.0].into_iter().collect();
+	/// let z: Bag<f64> = x.ordered_difference(&y);
+	/// assert_eq!(&[1.5, 2.0], &z.get_data()[..]);
+	/// ```
+	pub fn ordered_difference(&self, other: &Bag<T>) -> Bag<T> {
+		self.merged(other, |left, right| left.saturating_sub(right))
+	}
+
+	/// Returns the symmetric difference of two `bag` data structures, as `symmetric_difference`
+	/// does, for element types that are ordered rather than hashable. The result holds its
+	/// elements in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 1.5, 2.0].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 3.0].into_iter().collect();
+	/// let z: Bag<f64> = x.ordered_symmetric_difference(&y);
+	/// assert_eq!(&[1.5, 2.0, 3.0], &z.get_data()[..]);
+	/// ```
+	pub fn ordered_symmetric_difference(&self, other: &Bag<T>) -> Bag<T> {
+		self.merged(other, |left, right| left.abs_diff(right))
+	}
+
+	/// Returns a new `bag` holding each element of either bag as many times as `count`
+	/// returns for its multiplicities in this bag and in `other`.
+	fn merged<F: Fn(u64, u64) -> u64>(&self, other: &Bag<T>, count: F) -> Bag<T> {
+		let mut result = self.empty_like(1);
+
+		for (datum, left, right) in paired_runs(self, other) {
+			result.insert_n(datum.clone(), count(left, right));
+		}
+
+		result
+	}
+}
+
+impl<T: PartialOrd> Bag<T> {
+	/// Returns true if no element occurs more often in this bag than in `other`, as
+	/// `is_subbag_of` does, for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 2.0].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 1.5, 2.0, 3.0].into_iter().collect();
+	/// assert!(x.ordered_is_subbag_of(&y));
+	/// assert!(!y.ordered_is_subbag_of(&x));
+	/// ```
+	pub fn ordered_is_subbag_of(&self, other: &Bag<T>) -> bool {
+		if self.size() > other.size() { return false }
+
+		paired_runs(self, other).into_iter().all(|(_, left, right)| left <= right)
+	}
+
+	/// Returns true if no element occurs more often in `other` than in this bag, as
+	/// `is_superbag_of` does, for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 1.5, 2.0, 3.0].into_iter().collect();
+	/// let y: Bag<f64> = vec![1.5, 2.0].into_iter().collect();
+	/// assert!(x.ordered_is_superbag_of(&y));
+	/// ```
+	pub fn ordered_is_superbag_of(&self, other: &Bag<T>) -> bool {
+		other.ordered_is_subbag_of(self)
+	}
+
+	/// Returns true if the two bags have no element in common, as `is_disjoint` does,
+	/// for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, f64::NAN].into_iter().collect();
+	/// let y: Bag<f64> = vec![3.0, f64::NAN].into_iter().collect();
+	/// assert!(x.ordered_is_disjoint(&y));
+	/// assert!(!x.ordered_is_disjoint(&x));
+	/// ```
+	pub fn ordered_is_disjoint(&self, other: &Bag<T>) -> bool {
+		!paired_runs(self, other).into_iter().any(|(_, left, right)| left > 0 && right > 0)
+	}
+}
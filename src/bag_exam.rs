@@ -2,7 +2,7 @@ extern crate rand;
 
 use std::char;
 use self::rand::distributions::{IndependentSample, Range};
-use bag::Bag;
+use rust_adt::Bag;
 
 const MANY_TESTS: usize = 5;
 const POINTS: [i32; MANY_TESTS + 1] = [100, 32, 12, 12, 32, 12];
@@ -52,7 +52,7 @@ pub fn test1() -> i32 {
 	if !correct(&test, 5) { return 0 }
 
 	println!("\tThen checking occurrences of 2.0.");
-	if test.occurrences(2.0) != 2 {
+	if test.occurrences(&2.0) != 2 {
 		return 0
 	}
 	else {
@@ -68,7 +68,7 @@ pub fn test1() -> i32 {
 	println!("{}. Inserting the 2 more 2.0's into the bag.\n\tThen checking occurrences of 2.0's.\n", char::from_u32(test_letter as u32 + 7).unwrap());
 	test.insert(2.0);
 	test.insert(2.0);
-	if test.occurrences(2.0) != 4 {
+	if test.occurrences(&2.0) != 4 {
 		return 0
 	}
 	else {
@@ -76,7 +76,7 @@ pub fn test1() -> i32 {
 	}
 
 	println!("{}. Inserting {} random items between 0 and 49\n\tand then checking size.", char::from_u32(test_letter as u32 + 8).unwrap(), TEST_SIZE);
-	for i in 0..TEST_SIZE {
+	for _ in 0..TEST_SIZE {
 		let next = Range::new(0, 50);
 		let mut rng = rand::thread_rng();
 		let entry = next.ind_sample(&mut rng);
@@ -106,7 +106,7 @@ pub fn test2() -> i32 {
 	test.insert(1.0);
 	let copy2 = Bag::<f64>::new_from_bag(&test);
 	println!("\tand now testing the == method...");
-	if test.ne(&copy2) || copy2.ne(&test) {
+	if test != copy2 {
 		println!("Test failed.\n");
 		return 0;
 	}
@@ -146,7 +146,7 @@ pub fn test3() -> i32 {
 	let copy2 = Bag::<f64>::new_from_bag(&test);
 	test.insert(1.0);
 	println!("\taltering original by an insertion...");
-	if test.occurrences(1.0) != 5 || copy2.occurrences(1.0) != 4 {
+	if test.occurrences(&1.0) != 5 || copy2.occurrences(&1.0) != 4 {
 		println!("Test failed.");
 		return 0;
 	}
@@ -160,11 +160,11 @@ pub fn test3() -> i32 {
 	// test = test.clone();
 	// new_bytes.clone_from_slice(&test.get_data());
 	let test2 = test.clone();
-	if test2.ne(&test) {
+	if test2 != test {
 		println!("Test failed.");
 		return 0;
 	}
-	if test.ne(&test2) {
+	if test != test2 {
 		println!("Test failed.");
 		return 0;
 	}
@@ -183,7 +183,7 @@ pub fn test4() -> i32 {
 	let mut test = Bag::<f64>::new();
 
 	println!("Testing erase from empty bag (should have no effect) ...");
-	test.erase(0.0);
+	test.erase(&0.0);
 	if !correct(&test, 0) { return 0 }
 
 	println!("Inserting these: 8 6 10 1 7 10 15 3 13 2 5 11 14 4 12");
@@ -215,73 +215,73 @@ pub fn test4() -> i32 {
 	}
 
 	println!("Erasing 0 (which is not in bag, so bag should be unchanged) ...");
-	if test.erase_one(0.0) {
+	if test.erase_one(&0.0) {
 		println!("Test failed.");
 		return 0;
 	}
 	if !correct(&test, 15) { return 0 }
 
 	println!("Erasing the 6 ...");
-	test.erase(6.0);
+	test.erase(&6.0);
 	if !correct(&test, 14) { return 0 }
 
 	println!("Erasing one 10 ...");
-	if !test.erase_one(10.0) {
+	if !test.erase_one(&10.0) {
 		println!("Test failed.");
 		return 0;
 	}
 	if !correct(&test, 13) { return 0 }
 
 	println!("Erasing the 1 ...");
-	test.erase(1.0);
+	test.erase(&1.0);
 	if !correct(&test, 12) { return 0 }
 
 	println!("Erasing the 15 ...");
-	test.erase(15.0);
+	test.erase(&15.0);
 	if !correct(&test, 11) { return 0 }
 
 	println!("Erasing the 5 ...");
-	test.erase(5.0);
+	test.erase(&5.0);
 	if !correct(&test, 10) { return 0 }
 
 	println!("Erasing the 11 ...");
-	test.erase(11.0);
+	test.erase(&11.0);
 	if !correct(&test, 9) { return 0 }
 
 	println!("Erasing the 3 ...");
-	test.erase(3.0);
+	test.erase(&3.0);
 	if !correct(&test, 8) { return 0 }
 
 	println!("Erasing the 13 ...");
-	test.erase(13.0);
+	test.erase(&13.0);
 	if !correct(&test, 7) { return 0 }
 
 	println!("Erasing the 2 ...");
-	test.erase(2.0);
+	test.erase(&2.0);
 	if !correct(&test, 6) { return 0 }
 
 	println!("Erasing the 14 ...");
-	test.erase_one(14.0);
+	test.erase_one(&14.0);
 	if !correct(&test, 5) { return 0 }
 
 	println!("Erasing the 4 ...");
-	test.erase(4.0);
+	test.erase(&4.0);
 	if !correct(&test, 4) { return 0 }
 
 	println!("Erasing the 12 ...");
-	test.erase(12.0);
+	test.erase(&12.0);
 	if !correct(&test, 3) { return 0 }
 
 	println!("Erasing the 8 ...");
-	test.erase(8.0);
+	test.erase(&8.0);
 	if !correct(&test, 2) { return 0 }
 
 	println!("Erasing the 7 ...");
-	test.erase(7.0);
+	test.erase(&7.0);
 	if !correct(&test, 1) { return 0 }
 
 	println!("Erasing the other 10 ...");
-	if !test.erase_one(10.0) {
+	if !test.erase_one(&10.0) {
 		println!("Test failed ...");
 		return 0;
 	}
@@ -309,7 +309,7 @@ pub fn test4() -> i32 {
 	test.insert(5.0);
 	test.insert(5.0);
 	test.insert(5.0);
-	test.erase(5.0);
+	test.erase(&5.0);
 	if !correct(&test, 1) { 
 		// println!("{}", test);
 		return 0;
@@ -328,20 +328,18 @@ pub fn test4() -> i32 {
 pub fn test5() -> i32 {
 	let mut test1 = Bag::<f64>::new();
 	let mut test2 = Bag::<f64>::new();
-	let mut test3 = Bag::<f64>::new();
-	let mut test4 = Bag::<f64>::new();
 
 	println!("Inserting 2000 1's into test1 and 2000 2's into test2");
-	for i in 0..2000 {
+	for _ in 0..2000 {
 		test1.insert(1.0);
 		test2.insert(2.0);
 	}
 
 	println!("Now testing the AddAssign operation ...");
-	test3 = test2.clone();
-	test1.add_assign(test2);
+	let test3 = test2.clone();
+	test1 += test2;
 	println!("   and now testing for occurrences of 1's and 2's in test1");
-	if test1.occurrences(1.0) == 2000 && test1.occurrences(2.0) == 2000 {
+	if test1.occurrences(&1.0) == 2000 && test1.occurrences(&2.0) == 2000 {
 		println!("Test passed.");
 	}
 	else {
@@ -350,9 +348,9 @@ pub fn test5() -> i32 {
 	}
 
 	println!("Now testing the Add operation ...");
-	test4 = test1.add(test3);
+	let test4 = test1 + test3;
 	println!("   and now testing for occurrences of 2's in test3 ...");
-	if test4.occurrences(2.0) == 4000 {
+	if test4.occurrences(&2.0) == 4000 {
 		println!("Test passed.");
 	}
 	else {
@@ -1,7 +1,7 @@
 /// This is an interactive test program for the Bag abstract data type.
 /// A menu is displayed to show the user what options they have for testing.
 use std::io;
-use bag::Bag;
+use rust_adt::{Bag, DisplayOptions, TotalOrder};
 
 pub fn print_menu() {
 	println!("\n\n\tThe following choices are available with 2 bags: ");
@@ -25,12 +25,9 @@ pub fn get_user_command() -> char {
 	
 	match io::stdin().read_line(&mut user_input) {
 		Ok(_) => {
-			match user_input.chars().next() {
-				Some(c) => { return c; }
-				None => { return '*'; }
-			}
+			user_input.chars().next().unwrap_or('*')
 		}
-		Err(_) => { return '*';}
+		Err(_) => { '*' }
 	}
 }
 
@@ -41,35 +38,33 @@ pub fn get_number() -> f64 {
 	let trimmed = user_input.trim();
 	
 	match trimmed.parse::<f64>() {
-		Ok(f) => { return f }
+		Ok(f) => { f }
 		Err(..) => { 
 			println!("Incorrect type: expected <f64>.");
-			return 0.0
+			0.0
 		}
 	}
 }
 
 pub fn show_bags(b1: &Bag<f64>, b2: &Bag<f64>) {
-	println!("\t\tb1 {:?}", b1);
-	println!("\t\tb2 {:?}", b2);
+	let options = DisplayOptions::new().max_elements(Some(20));
+	println!("\t\tb1 {} (capacity {})", b1.display_with(options).grouped(true).sorted(true), b1.get_capacity());
+	println!("\t\tb2 {} (capacity {})", b2.display_with(options).grouped(true).sorted(true), b2.get_capacity());
 }
 
 pub fn copybag(b: &Bag<f64>) -> Bag<f64> {
-	let return_bag = Bag::<f64>::new_from_bag(&b);
-	return_bag
+	Bag::<f64>::new_from_bag(b)
 }
 
 pub fn interactive_main() {
 	let mut b1 = Bag::<f64>::new();
 	let mut b2 = Bag::<f64>::new();
-	let mut choice = 'z';
-	let mut done = false;
 
 	println!("\tI have initialized two empty bags of doubles.");
 
-	while !done {
+	loop {
 		print_menu();
-		choice = get_user_command();
+		let choice = get_user_command();
 		match choice {
 			'A' => {
 				b1 = b2.clone();
@@ -92,17 +87,18 @@ pub fn interactive_main() {
 			'i' => {
 				b2.insert(get_number());
 			}
+			// Match entries by their exact bits, so that a NaN that was inserted can be erased.
 			'R' => {
-				b1.erase(get_number());
+				b1.erase_by(&get_number(), &TotalOrder);
 			}
 			'r' => {
-				b2.erase(get_number());
+				b2.erase_by(&get_number(), &TotalOrder);
 			}
 			'X' => {
-				b1.erase_one(get_number());
+				b1.erase_one_by(&get_number(), &TotalOrder);
 			}
 			'x' => {
-				b2.erase_one(get_number());
+				b2.erase_one_by(&get_number(), &TotalOrder);
 			}
 			'O' | 'o' => {
 				show_bags(&b1, &b2);
@@ -0,0 +1,311 @@
+//! A compact binary encoding of the `Bag` type, for caching bags on disk.
+//! 
+//! `write_to` stores each distinct element once, followed by the number of times it
+//! occurs, so a bag of 4000 elements holding two distinct values takes a few dozen bytes.
+//! All integers are little-endian. The layout is:
+//! 
+//! ```text
+//! magic      4 bytes   "RBAG"
+//! version    1 byte    1
+//! capacity   u64
+//! runs       u64       the number of distinct elements that follow
+//! run        runs times: the encoded element, then its count as a u64
+//! checksum   u64       FNV-1a of every byte above
+//! ```
+//! 
+//! Elements are grouped by their encoded bytes, so two floats group together only if
+//! they have identical bits. The growth policy and shrink threshold of a bag are not
+//! stored; `read_from` returns a bag with the defaults.
+//! 
+//! The checksum catches accidental corruption, not deliberate tampering, so `read_from`
+//! does not trust the stored capacity: it only checks that the counts fit in it, and
+//! returns a bag with exactly the capacity its elements need, or 1 if there are none.
+//! Nor does it trust the counts, whose total may not exceed `MAX_TOTAL_COUNT` unless
+//! `read_from_with_limit` allows more.
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::mem;
+
+use {Bag, MAX_TOTAL_COUNT};
+
+/// The bytes every encoded bag starts with.
+const MAGIC: &[u8; 4] = b"RBAG";
+
+/// The version of the layout written by `write_to`.
+const VERSION: u8 = 1;
+
+/// Element types that can be written by `Bag::write_to`.
+pub trait Encode {
+	/// Writes the value to `writer`.
+	fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+/// Element types that can be read by `Bag::read_from`.
+pub trait Decode: Sized {
+	/// Reads a value written by `Encode::encode` from `reader`.
+	fn decode<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! little_endian {
+	($($number:ty),*) => {$(
+		impl Encode for $number {
+			fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+				writer.write_all(&self.to_le_bytes())
+			}
+		}
+
+		impl Decode for $number {
+			fn decode<R: Read>(reader: &mut R) -> io::Result<$number> {
+				let mut bytes = [0; mem::size_of::<$number>()];
+				reader.read_exact(&mut bytes)?;
+
+				Ok(<$number>::from_le_bytes(bytes))
+			}
+		}
+	)*}
+}
+
+little_endian!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl Encode for bool {
+	fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		(*self as u8).encode(writer)
+	}
+}
+
+impl Decode for bool {
+	fn decode<R: Read>(reader: &mut R) -> io::Result<bool> {
+		match u8::decode(reader)? {
+			0 => Ok(false),
+			1 => Ok(true),
+			_ => Err(invalid_data("invalid bool")),
+		}
+	}
+}
+
+impl Encode for char {
+	fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		(*self as u32).encode(writer)
+	}
+}
+
+impl Decode for char {
+	fn decode<R: Read>(reader: &mut R) -> io::Result<char> {
+		match ::std::char::from_u32(u32::decode(reader)?) {
+			Some(value) => Ok(value),
+			None => Err(invalid_data("invalid char")),
+		}
+	}
+}
+
+impl Encode for String {
+	fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		(self.len() as u64).encode(writer)?;
+		writer.write_all(self.as_bytes())
+	}
+}
+
+impl Decode for String {
+	fn decode<R: Read>(reader: &mut R) -> io::Result<String> {
+		let length = u64::decode(reader)?;
+		let mut bytes = Vec::new();
+		reader.take(length).read_to_end(&mut bytes)?;
+
+		if (bytes.len() as u64) < length {
+			return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "string ends early"));
+		}
+
+		String::from_utf8(bytes).map_err(|_| invalid_data("invalid UTF-8 in string"))
+	}
+}
+
+impl<T: Encode> Bag<T> {
+	/// Writes the bag to `writer` in the binary layout described in the module documentation.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert_n(1.0, 2000);
+	/// x.insert_n(2.0, 2000);
+	/// let mut bytes = Vec::new();
+	/// x.write_to(&mut bytes).unwrap();
+	/// assert_eq!(61, bytes.len());
+	/// ```
+	pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		let mut positions: HashMap<Vec<u8>, usize> = HashMap::new();
+		let mut runs: Vec<(Vec<u8>, u64)> = Vec::new();
+
+		for datum in &self.data {
+			let mut encoded = Vec::new();
+			datum.encode(&mut encoded)?;
+
+			match positions.get(&encoded) {
+				Some(&position) => runs[position].1 += 1,
+				None => {
+					positions.insert(encoded.clone(), runs.len());
+					runs.push((encoded, 1));
+				}
+			}
+		}
+
+		let mut writer = Checksummed::new(writer);
+		writer.write_all(MAGIC)?;
+		VERSION.encode(&mut writer)?;
+		self.capacity.encode(&mut writer)?;
+		(runs.len() as u64).encode(&mut writer)?;
+
+		for (encoded, count) in runs {
+			writer.write_all(&encoded)?;
+			count.encode(&mut writer)?;
+		}
+
+		let checksum = writer.checksum;
+		checksum.encode(writer.inner)
+	}
+}
+
+impl<T: Decode + Clone> Bag<T> {
+	/// Reads a bag written by `write_to` from `reader`.
+	/// 
+	/// Fails with `io::ErrorKind::InvalidData` if the magic bytes, version or checksum
+	/// are wrong, if the counts are inconsistent with the capacity, or if they add up to
+	/// more than `MAX_TOTAL_COUNT`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![7, 3, 7].into_iter().collect();
+	/// let mut bytes = Vec::new();
+	/// x.write_to(&mut bytes).unwrap();
+	/// 
+	/// let y: Bag<u32> = Bag::<u32>::read_from(&mut &bytes[..]).unwrap();
+	/// assert_eq!(x, y);
+	/// assert_eq!(4, x.get_capacity());
+	/// assert_eq!(3, y.get_capacity());
+	/// 
+	/// bytes[10] ^= 1;
+	/// assert!(Bag::<u32>::read_from(&mut &bytes[..]).is_err());
+	/// 
+	/// let mut bytes = Vec::new();
+	/// Bag::<u32>::new_with_capacity(1000).write_to(&mut bytes).unwrap();
+	/// let z: Bag<u32> = Bag::<u32>::read_from(&mut &bytes[..]).unwrap();
+	/// assert_eq!(0, z.size());
+	/// assert_eq!(1, z.get_capacity());
+	/// 
+	/// let mut bytes = Vec::new();
+	/// Bag::<u32>::default().write_to(&mut bytes).unwrap();
+	/// assert!(Bag::<u32>::read_from(&mut &bytes[..]).is_ok());
+	/// ```
+	pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Bag<T>> {
+		Bag::<T>::read_from_with_limit(reader, MAX_TOTAL_COUNT)
+	}
+
+	/// Reads a bag written by `write_to` from `reader`, as `read_from` does, but rejects
+	/// counts adding up to more than `max_total_count` instead of `MAX_TOTAL_COUNT`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![7, 3, 7].into_iter().collect();
+	/// let mut bytes = Vec::new();
+	/// x.write_to(&mut bytes).unwrap();
+	/// 
+	/// assert_eq!(x, Bag::<u32>::read_from_with_limit(&mut &bytes[..], 3).unwrap());
+	/// let error = Bag::<u32>::read_from_with_limit(&mut &bytes[..], 2).unwrap_err();
+	/// assert_eq!("total count exceeds 2", error.to_string());
+	/// ```
+	pub fn read_from_with_limit<R: Read>(reader: &mut R, max_total_count: u64) -> io::Result<Bag<T>> {
+		let mut reader = Checksummed::new(reader);
+		let mut magic = [0; 4];
+		reader.read_exact(&mut magic)?;
+
+		if &magic != MAGIC { return Err(invalid_data("not an encoded bag")) }
+
+		let version = u8::decode(&mut reader)?;
+
+		if version != VERSION { return Err(invalid_data("unsupported version")) }
+
+		let capacity = u64::decode(&mut reader)?;
+		let runs = u64::decode(&mut reader)?;
+		let mut decoded = Vec::new();
+		let mut total: u64 = 0;
+
+		for _ in 0..runs {
+			let datum = T::decode(&mut reader)?;
+			let count = u64::decode(&mut reader)?;
+
+			total = match total.checked_add(count) {
+				Some(total) if count > 0 && total <= capacity => total,
+				_ => return Err(invalid_data("counts are inconsistent with the capacity")),
+			};
+
+			if total > max_total_count {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, format!("total count exceeds {}", max_total_count)));
+			}
+
+			decoded.push((datum, count));
+		}
+
+		let checksum = reader.checksum;
+
+		if u64::decode(reader.inner)? != checksum { return Err(invalid_data("checksum mismatch")) }
+
+		let mut bag = Bag::<T>::try_new_with_capacity(if total < 1 { 1 } else { total }).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+		for (datum, count) in decoded {
+			bag.insert_n(datum, count);
+		}
+
+		Ok(bag)
+	}
+}
+
+/// Wraps a reader or writer and keeps an FNV-1a checksum of the bytes passing through it.
+struct Checksummed<'a, S: 'a> {
+	inner: &'a mut S,
+	checksum: u64,
+}
+
+impl<'a, S> Checksummed<'a, S> {
+	/// Wraps `inner`, starting from the FNV-1a offset basis.
+	fn new(inner: &'a mut S) -> Checksummed<'a, S> {
+		Checksummed { inner, checksum: 0xcbf2_9ce4_8422_2325 }
+	}
+
+	/// Adds the bytes to the checksum.
+	fn update(&mut self, bytes: &[u8]) {
+		for &byte in bytes {
+			self.checksum ^= byte as u64;
+			self.checksum = self.checksum.wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+}
+
+impl<'a, R: Read> Read for Checksummed<'a, R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let count = self.inner.read(buf)?;
+		self.update(&buf[..count]);
+		Ok(count)
+	}
+}
+
+impl<'a, W: Write> Write for Checksummed<'a, W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let count = self.inner.write(buf)?;
+		self.update(&buf[..count]);
+		Ok(count)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+/// Returns an `io::Error` of kind `InvalidData` with the given message.
+fn invalid_data(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,122 @@
+//! Functional combinators that build new bags from the elements of a `Bag`.
+//! 
+//! The bags they return keep the growth policy and shrink threshold of the source bag.
+//! `map` knows its result has exactly as many elements as the source, so it reserves
+//! the source's capacity up front; the others grow through `insert` as usual.
+use Bag;
+
+impl<T: Clone> Bag<T> {
+	/// Returns a new `bag` holding clones of the elements for which the predicate returns true.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, -1.0, -3.0].into_iter().collect();
+	/// let y: Bag<f64> = x.filter(|value| *value < 0.0);
+	/// assert_eq!(2, y.size());
+	/// assert_eq!(3, x.size());
+	/// ```
+	pub fn filter<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Bag<T> {
+		let mut result = self.empty_like(1);
+
+		for datum in &self.data {
+			if predicate(datum) {
+				result.insert(datum.clone());
+			}
+		}
+
+		result
+	}
+}
+
+impl<T> Bag<T> {
+	/// Returns a new `bag` holding the result of applying the function to each element.
+	/// 
+	/// The new bag has the same capacity as this one.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![0.4, 1.7, 1.2].into_iter().collect();
+	/// let buckets: Bag<i64> = x.map(|value| value.floor() as i64);
+	/// assert_eq!(2, buckets.occurrences(&1));
+	/// assert_eq!(x.get_capacity(), buckets.get_capacity());
+	/// ```
+	pub fn map<U, F: FnMut(&T) -> U>(&self, function: F) -> Bag<U> {
+		let mut result = self.empty_like(self.capacity);
+
+		result.data.extend(self.data.iter().map(function));
+		result.used = self.used;
+
+		result
+	}
+
+	/// Returns a new `bag` holding the values the function returns as `Some`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, -1.0, 9.0].into_iter().collect();
+	/// let roots: Bag<i64> = x.filter_map(|value| if *value >= 0.0 { Some(value.sqrt() as i64) } else { None });
+	/// assert_eq!(2, roots.size());
+	/// assert_eq!(1, roots.occurrences(&3));
+	/// ```
+	pub fn filter_map<U, F: FnMut(&T) -> Option<U>>(&self, mut function: F) -> Bag<U> {
+		let mut result = self.empty_like(1);
+
+		for datum in &self.data {
+			if let Some(value) = function(datum) {
+				result.insert(value);
+			}
+		}
+
+		result
+	}
+
+	/// Returns a new `bag` holding every value produced by the iterators the function returns.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![2, 3].into_iter().collect();
+	/// let labels: Bag<String> = x.flat_map(|count| (0..*count).map(|i| format!("item {}", i)));
+	/// assert_eq!(5, labels.size());
+	/// assert_eq!(2, labels.occurrences("item 0"));
+	/// ```
+	pub fn flat_map<U, I: IntoIterator<Item = U>, F: FnMut(&T) -> I>(&self, mut function: F) -> Bag<U> {
+		let mut result = self.empty_like(1);
+
+		for datum in &self.data {
+			result.extend(function(datum));
+		}
+
+		result
+	}
+
+	/// Combines every element into a single value, starting from `init`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.0, 2.0, 3.0].into_iter().collect();
+	/// assert_eq!(6.0, x.fold(0.0, |total, value| total + value));
+	/// ```
+	pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, function: F) -> B {
+		self.data.iter().fold(init, function)
+	}
+
+	/// Returns an empty `bag` of the given capacity with the same growth policy and
+	/// shrink threshold as this one.
+	pub(crate) fn empty_like<U>(&self, capacity: u64) -> Bag<U> {
+		let mut result = Bag::<U>::new_with_capacity(if capacity < 1 { 1 } else { capacity });
+		result.growth = self.growth.clone();
+		result.shrink_below = self.shrink_below;
+
+		result
+	}
+}
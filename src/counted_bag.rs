@@ -0,0 +1,267 @@
+//! # CountedBag
+//! 
+//! A bag that is represented by two basic structures.
+//! 
+//!   1. `counts` is a HashMap from each distinct value to the number of times it occurs.
+//!   2. `used` is an integer that determines how many elements the bag holds in total.
+//! 
+//! Storing a count per distinct value lets `insert`, `occurrences`, `erase` and `erase_one`
+//! run in constant time no matter how many duplicates the bag holds.
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign};
+
+use Bag;
+
+/// The struct for the `CountedBag` data type.
+/// 
+/// This struct derives the Default, Clone, Debug, PartialEq and Eq traits.
+/// Two counted bags are equal when they hold the same elements with the same multiplicities.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct CountedBag<T: Hash + Eq> {
+	counts: HashMap<T, u64>,
+	used: u64,
+}
+
+impl<T: Hash + Eq> AddAssign<CountedBag<T>> for CountedBag<T> {
+	/// Performs the `+=` operation on a `counted bag` using the `counted bag` passed as an argument.
+	/// 
+	/// Adds the multiplicities of the argument to the `counted bag` calling the function.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// let mut y: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// y.insert(1);
+	/// y.insert(2);
+	/// x += y;
+	/// assert_eq!(3, x.size());
+	/// assert_eq!(2, x.occurrences(&1));
+	/// ```
+	fn add_assign(&mut self, addend: CountedBag<T>) {
+		for (value, count) in addend.counts {
+			*self.counts.entry(value).or_insert(0) += count;
+		}
+
+		self.used += addend.used;
+	}
+}
+
+impl<T: Hash + Eq> Add<CountedBag<T>> for CountedBag<T> {
+	type Output = CountedBag<T>;
+
+	/// Adds two `counted bag` data structures together.
+	/// 
+	/// Returns a new `counted bag` whose multiplicities are the sums of those of the two operands.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// let mut y: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// y.insert(1);
+	/// let z: CountedBag<u32> = x + y;
+	/// assert_eq!(2, z.occurrences(&1));
+	/// ```
+	fn add(self, addend: CountedBag<T>) -> CountedBag<T> {
+		let mut new_bag = self;
+		new_bag += addend;
+
+		new_bag
+	}
+}
+
+impl<T: Hash + Eq> From<Bag<T>> for CountedBag<T> {
+	/// Builds a `counted bag` holding the same elements as the `bag`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, CountedBag};
+	/// let mut x: Bag<u32> = Bag::<u32>::new();
+	/// x.insert(7);
+	/// x.insert(7);
+	/// let y: CountedBag<u32> = CountedBag::from(x);
+	/// assert_eq!(2, y.occurrences(&7));
+	/// ```
+	fn from(source: Bag<T>) -> CountedBag<T> {
+		let mut bag = CountedBag::<T>::new();
+
+		for entry in source {
+			bag.insert(entry);
+		}
+
+		bag
+	}
+}
+
+impl<T: Hash + Eq + Clone> From<CountedBag<T>> for Bag<T> {
+	/// Builds a `bag` holding every copy of every element of the `counted bag`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, CountedBag};
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(7);
+	/// x.insert(7);
+	/// let y: Bag<u32> = Bag::from(x);
+	/// assert_eq!(2, y.size());
+	/// assert_eq!(2, y.occurrences(&7));
+	/// ```
+	fn from(source: CountedBag<T>) -> Bag<T> {
+		let mut bag = Bag::<T>::new();
+
+		for (value, count) in source.counts {
+			for _ in 1..count {
+				bag.insert(value.clone());
+			}
+
+			bag.insert(value);
+		}
+
+		bag
+	}
+}
+
+impl<T: Hash + Eq> CountedBag<T> {
+	/// Returns a new, empty `CountedBag`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// assert_eq!(0, x.size());
+	/// ```
+	pub fn new() -> CountedBag<T> {
+		CountedBag::<T> {
+			counts: HashMap::new(),
+			used: 0,
+		}
+	}
+
+	/// Returns the map from each distinct element to its number of occurrences.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// assert_eq!(Some(&2), x.get_counts().get(&1));
+	/// ```
+	pub fn get_counts(&self) -> &HashMap<T, u64> {
+		&self.counts
+	}
+
+	/// Returns the total number of elements in the bag, counting duplicates.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn size(&self) -> u64 {
+		self.used
+	}
+
+	/// Inserts an element into the bag in constant time.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn insert(&mut self, entry: T) {
+		*self.counts.entry(entry).or_insert(0) += 1;
+		self.used += 1;
+	}
+
+	/// Determines the number of occurrences of the target element in constant time.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(15);
+	/// x.insert(5);
+	/// x.insert(15);
+	/// assert_eq!(2, x.occurrences(&15));
+	/// ```
+	pub fn occurrences<Q: ?Sized + Hash + Eq>(&self, target: &Q) -> u64 where T: Borrow<Q> {
+		match self.counts.get(target) {
+			Some(count) => *count,
+			None => 0,
+		}
+	}
+
+	/// Returns the number of erased elements matching the given target.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(2, x.erase(&1));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase<Q: ?Sized + Hash + Eq>(&mut self, target: &Q) -> u64 where T: Borrow<Q> {
+		match self.counts.remove(target) {
+			Some(count) => {
+				self.used -= count;
+				count
+			}
+			None => 0,
+		}
+	}
+
+	/// Returns a boolean indicating whether or not the target was found and erased.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::CountedBag;
+	/// let mut x: CountedBag<u32> = CountedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(true, x.erase_one(&1));
+	/// assert_eq!(false, x.erase_one(&5));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase_one<Q: ?Sized + Hash + Eq>(&mut self, target: &Q) -> bool where T: Borrow<Q> {
+		let remaining = match self.counts.get_mut(target) {
+			Some(count) => {
+				*count -= 1;
+				*count
+			}
+			None => return false,
+		};
+
+		if remaining == 0 {
+			self.counts.remove(target);
+		}
+
+		self.used -= 1;
+		true
+	}
+}
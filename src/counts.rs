@@ -0,0 +1,336 @@
+//! Views of a `Bag` as its distinct elements and their multiplicities.
+//! 
+//! Hashable element types are counted with a HashMap in linear time. Ordered element
+//! types can be counted into a BTreeMap instead, which yields the elements in order.
+//! 
+//! The frequency queries rank the counted elements with a heap holding at most `k`
+//! of them, so they run in O(n log k) time. Elements with equal counts are ranked
+//! in order of first appearance.
+//! 
+//! Element types that are ordered but not hashable, such as `f64`, have the same
+//! frequency queries with an `ordered_` prefix. These count runs of equal elements
+//! after sorting, so they take O(n log n) time. Values that cannot be compared even
+//! with themselves, such as NaN, are counted together as one element.
+use std::cmp::{Ordering, Reverse};
+use std::collections::{btree_map, BinaryHeap, BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::vec;
+
+use Bag;
+
+impl<T: Hash + Eq> Bag<T> {
+	/// Returns an iterator over the distinct elements of the bag, in order of first appearance.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![3, 1, 3, 2, 1].into_iter().collect();
+	/// let distinct: Vec<&u32> = x.distinct().collect();
+	/// assert_eq!(vec![&3, &1, &2], distinct);
+	/// ```
+	pub fn distinct(&self) -> impl Iterator<Item = &T> {
+		let mut seen = HashSet::new();
+
+		self.data.iter().filter(move |datum| seen.insert(*datum))
+	}
+
+	/// Returns the number of distinct elements in the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![3, 1, 3, 2, 1].into_iter().collect();
+	/// assert_eq!(3, x.distinct_len());
+	/// ```
+	pub fn distinct_len(&self) -> u64 {
+		self.data.iter().collect::<HashSet<&T>>().len() as u64
+	}
+
+	/// Returns an iterator over each distinct element and the number of times it occurs,
+	/// in order of first appearance.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![3, 1, 3, 2, 1, 3].into_iter().collect();
+	/// let counts: Vec<(&u32, u64)> = x.counts().collect();
+	/// assert_eq!(vec![(&3, 3), (&1, 2), (&2, 1)], counts);
+	/// ```
+	pub fn counts(&self) -> vec::IntoIter<(&T, u64)> {
+		let mut positions: HashMap<&T, usize> = HashMap::new();
+		let mut counts: Vec<(&T, u64)> = Vec::new();
+
+		for datum in &self.data {
+			let next = counts.len();
+			let position = *positions.entry(datum).or_insert(next);
+
+			if position == next {
+				counts.push((datum, 1));
+			}
+			else {
+				counts[position].1 += 1;
+			}
+		}
+
+		counts.into_iter()
+	}
+
+	/// Consumes the bag and returns a map from each distinct element to the number of times it occurs.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<String> = vec!["a".to_string(), "b".to_string(), "a".to_string()].into_iter().collect();
+	/// let counts = x.into_counts();
+	/// assert_eq!(Some(&2), counts.get("a"));
+	/// assert_eq!(Some(&1), counts.get("b"));
+	/// ```
+	pub fn into_counts(self) -> HashMap<T, u64> {
+		let mut counts = HashMap::new();
+
+		for datum in self.data {
+			*counts.entry(datum).or_insert(0) += 1;
+		}
+
+		counts
+	}
+
+	/// Returns the `k` elements that occur most often, with their counts, from most to least common.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<char> = "abracadabra".chars().collect();
+	/// assert_eq!(vec![(&'a', 5), (&'b', 2), (&'r', 2)], x.most_common(3));
+	/// ```
+	pub fn most_common(&self, k: usize) -> Vec<(&T, u64)> {
+		top_k(self.counts().collect(), k, |count, position| (count, Reverse(position)))
+	}
+
+	/// Returns the `k` elements that occur least often, with their counts, from least to most common.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<char> = "abracadabra".chars().collect();
+	/// assert_eq!(vec![(&'c', 1), (&'d', 1)], x.least_common(2));
+	/// ```
+	pub fn least_common(&self, k: usize) -> Vec<(&T, u64)> {
+		top_k(self.counts().collect(), k, |count, position| (Reverse(count), Reverse(position)))
+	}
+
+	/// Returns every element that occurs as often as the most common one, in order of first appearance.
+	/// 
+	/// The result holds more than one element when several tie, and is empty if the bag is empty.
+	/// For numeric bags, `Statistics::modes_by_value` returns copies sorted by value instead.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![2, 1, 2, 1, 3].into_iter().collect();
+	/// assert_eq!(vec![&2, &1], x.mode());
+	/// assert!(Bag::<u32>::new().mode().is_empty());
+	/// ```
+	pub fn mode(&self) -> Vec<&T> {
+		most_frequent(self.counts().collect())
+	}
+
+	/// Returns an iterator over each distinct element that occurs at least `min_count` times,
+	/// with its count, in order of first appearance.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<char> = "abracadabra".chars().collect();
+	/// let frequent: Vec<(&char, u64)> = x.threshold(2).collect();
+	/// assert_eq!(vec![(&'a', 5), (&'b', 2), (&'r', 2)], frequent);
+	/// ```
+	pub fn threshold(&self, min_count: u64) -> impl Iterator<Item = (&T, u64)> {
+		self.counts().filter(move |&(_, count)| count >= min_count)
+	}
+}
+
+/// Returns the `k` counted elements with the greatest rank, greatest first.
+/// 
+/// The counts must be in order of first appearance. `rank` maps an element's count and
+/// position of first appearance to a key; a heap of at most `k` entries keeps the best
+/// keys seen so far.
+fn top_k<T, K: Ord, F: Fn(u64, usize) -> K>(counts: Vec<(&T, u64)>, k: usize, rank: F) -> Vec<(&T, u64)> {
+	if k == 0 { return Vec::new() }
+
+	let mut heap = BinaryHeap::with_capacity(if k < counts.len() { k + 1 } else { counts.len() });
+
+	for (position, &(_, count)) in counts.iter().enumerate() {
+		heap.push(Reverse((rank(count, position), position)));
+
+		if heap.len() > k {
+			heap.pop();
+		}
+	}
+
+	heap.into_sorted_vec().into_iter().map(|Reverse((_, position))| counts[position]).collect()
+}
+
+/// Returns every counted element whose count is the highest, in the order of `counts`.
+fn most_frequent<T>(counts: Vec<(&T, u64)>) -> Vec<&T> {
+	let highest = counts.iter().map(|&(_, count)| count).max().unwrap_or(0);
+
+	counts.into_iter().filter(|&(_, count)| count == highest).map(|(datum, _)| datum).collect()
+}
+
+impl<T: PartialOrd> Bag<T> {
+	/// Returns the `k` elements that occur most often, with their counts, from most to least
+	/// common, as `most_common` does, for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![2.5, 1.0, 2.5, 3.0, 1.0, 2.5].into_iter().collect();
+	/// assert_eq!(vec![(&2.5, 3), (&1.0, 2)], x.ordered_most_common(2));
+	/// ```
+	pub fn ordered_most_common(&self, k: usize) -> Vec<(&T, u64)> {
+		top_k(self.grouped_runs(), k, |count, position| (count, Reverse(position)))
+	}
+
+	/// Returns the `k` elements that occur least often, with their counts, from least to most
+	/// common, as `least_common` does, for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![2.5, 1.0, 2.5, 3.0, 1.0, 2.5].into_iter().collect();
+	/// assert_eq!(vec![(&3.0, 1), (&1.0, 2)], x.ordered_least_common(2));
+	/// ```
+	pub fn ordered_least_common(&self, k: usize) -> Vec<(&T, u64)> {
+		top_k(self.grouped_runs(), k, |count, position| (Reverse(count), Reverse(position)))
+	}
+
+	/// Returns every element that occurs as often as the most common one, in order of first
+	/// appearance, as `mode` does, for element types that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![2.5, 1.0, 2.5, 1.0, 3.0].into_iter().collect();
+	/// assert_eq!(vec![&2.5, &1.0], x.ordered_mode());
+	/// assert!(Bag::<f64>::new().ordered_mode().is_empty());
+	/// ```
+	pub fn ordered_mode(&self) -> Vec<&T> {
+		most_frequent(self.grouped_runs())
+	}
+
+	/// Returns an iterator over each distinct element that occurs at least `min_count` times,
+	/// with its count, in order of first appearance, as `threshold` does, for element types
+	/// that are ordered rather than hashable.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![2.5, f64::NAN, 1.0, 2.5, f64::NAN].into_iter().collect();
+	/// let frequent: Vec<(&f64, u64)> = x.ordered_threshold(2).collect();
+	/// assert_eq!(2, frequent.len());
+	/// assert_eq!((&2.5, 2), frequent[0]);
+	/// assert!(frequent[1].0.is_nan());
+	/// ```
+	pub fn ordered_threshold(&self, min_count: u64) -> impl Iterator<Item = (&T, u64)> {
+		self.grouped_runs().into_iter().filter(move |&(_, count)| count >= min_count)
+	}
+
+	/// Returns each distinct element and the number of times it occurs, in ascending order.
+	/// 
+	/// Elements that cannot be compared even with themselves, such as NaN, are placed last
+	/// and counted together.
+	pub(crate) fn sorted_runs(&self) -> Vec<(&T, u64)> {
+		self.indexed_runs().into_iter().map(|(_, datum, count)| (datum, count)).collect()
+	}
+
+	/// Returns each distinct element and the number of times it occurs, in order of first
+	/// appearance, counting the elements as `sorted_runs` does.
+	pub(crate) fn grouped_runs(&self) -> Vec<(&T, u64)> {
+		let mut runs = self.indexed_runs();
+		runs.sort_by_key(|&(first, _, _)| first);
+
+		runs.into_iter().map(|(_, datum, count)| (datum, count)).collect()
+	}
+
+	/// Returns each distinct element in ascending order, with the position of its first
+	/// appearance and the number of times it occurs.
+	fn indexed_runs(&self) -> Vec<(usize, &T, u64)> {
+		let unordered = |value: &T| value.partial_cmp(value).is_none();
+		let mut sorted: Vec<(usize, &T)> = self.data.iter().enumerate().collect();
+
+		sorted.sort_by(|&(_, a), &(_, b)| match (unordered(a), unordered(b)) {
+			(true, true) => Ordering::Equal,
+			(true, false) => Ordering::Greater,
+			(false, true) => Ordering::Less,
+			(false, false) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+		});
+
+		let mut runs: Vec<(usize, &T, u64)> = Vec::new();
+
+		for (index, datum) in sorted {
+			match runs.last_mut() {
+				Some(&mut (_, last, ref mut count)) if last == datum || (unordered(last) && unordered(datum)) => *count += 1,
+				_ => runs.push((index, datum, 1)),
+			}
+		}
+
+		runs
+	}
+}
+
+impl<T: Ord> Bag<T> {
+	/// Returns an iterator over each distinct element and the number of times it occurs,
+	/// in ascending order of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![3, 1, 3, 2, 1, 3].into_iter().collect();
+	/// let counts: Vec<(&u32, u64)> = x.ordered_counts().collect();
+	/// assert_eq!(vec![(&1, 2), (&2, 1), (&3, 3)], counts);
+	/// ```
+	pub fn ordered_counts(&self) -> btree_map::IntoIter<&T, u64> {
+		let mut counts = BTreeMap::new();
+
+		for datum in &self.data {
+			*counts.entry(datum).or_insert(0) += 1;
+		}
+
+		counts.into_iter()
+	}
+
+	/// Consumes the bag and returns an ordered map from each distinct element to the number
+	/// of times it occurs.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u32> = vec![3, 1, 3].into_iter().collect();
+	/// let counts = x.into_ordered_counts();
+	/// assert_eq!(vec![(&1, &1), (&3, &2)], counts.iter().collect::<Vec<_>>());
+	/// ```
+	pub fn into_ordered_counts(self) -> BTreeMap<T, u64> {
+		let mut counts = BTreeMap::new();
+
+		for datum in self.data {
+			*counts.entry(datum).or_insert(0) += 1;
+		}
+
+		counts
+	}
+}
@@ -0,0 +1,405 @@
+//! Importing a `Bag` from CSV data and exporting it as value,count rows.
+//! 
+//! Fields are quoted as RFC 4180 describes. A field between double quotes may hold the
+//! delimiter, line breaks and quotes, which are doubled; `write_csv` quotes every value
+//! that needs it. Unquoted fields are trimmed, then every field is parsed with the element
+//! type's `FromStr`, as `bag_test::get_number` does.
+//! 
+//! Only a quote that begins a field opens a quoted field; a quote inside an unquoted field
+//! is an error rather than the start of a field spanning the following lines.
+//! 
+//! Blank lines are skipped, and rows are numbered from 1, counting the header and blank
+//! lines, so errors match a spreadsheet's row numbers. A row whose quoted field holds
+//! line breaks is still one row. The counts read may add up to at most `MAX_TOTAL_COUNT`
+//! elements, unless `CsvOptions::max_total_count` allows another total.
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use {Bag, MAX_TOTAL_COUNT};
+
+/// The order of the rows written by `Bag::write_csv`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvOrder {
+	/// Ascending by value.
+	Value,
+	/// Descending by count, and ascending by value among equal counts.
+	Count,
+}
+
+/// Options for reading and writing a `Bag` as CSV.
+/// 
+/// By default the delimiter is a comma, there is no header row, values are read from
+/// the first column with each row counting once, at most `MAX_TOTAL_COUNT` elements are
+/// read, and rows are written in value order.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{CsvOptions, CsvOrder};
+/// let options = CsvOptions::new().delimiter(';').has_header(true).order(CsvOrder::Count);
+/// assert_ne!(CsvOptions::new(), options);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+	delimiter: char,
+	has_header: bool,
+	value_column: usize,
+	count_column: Option<usize>,
+	max_total_count: u64,
+	order: CsvOrder,
+}
+
+impl Default for CsvOptions {
+	fn default() -> CsvOptions {
+		CsvOptions::new()
+	}
+}
+
+impl CsvOptions {
+	/// Returns the default options.
+	pub fn new() -> CsvOptions {
+		CsvOptions {
+			delimiter: ',',
+			has_header: false,
+			value_column: 0,
+			count_column: None,
+			max_total_count: MAX_TOTAL_COUNT,
+			order: CsvOrder::Value,
+		}
+	}
+
+	/// Sets the character that separates fields.
+	pub fn delimiter(mut self, delimiter: char) -> CsvOptions {
+		self.delimiter = delimiter;
+		self
+	}
+
+	/// Sets whether the first row is a header, which is skipped when reading and
+	/// written as `value,count` when writing.
+	pub fn has_header(mut self, has_header: bool) -> CsvOptions {
+		self.has_header = has_header;
+		self
+	}
+
+	/// Sets the zero-based column that values are read from.
+	pub fn value_column(mut self, column: usize) -> CsvOptions {
+		self.value_column = column;
+		self
+	}
+
+	/// Sets the zero-based column that counts are read from, or `None` to count each row once.
+	pub fn count_column(mut self, column: Option<usize>) -> CsvOptions {
+		self.count_column = column;
+		self
+	}
+
+	/// Sets the most elements `Bag::read_csv` reads, adding up the counts of every row.
+	pub fn max_total_count(mut self, max_total_count: u64) -> CsvOptions {
+		self.max_total_count = max_total_count;
+		self
+	}
+
+	/// Sets the order of the rows written by `Bag::write_csv`.
+	pub fn order(mut self, order: CsvOrder) -> CsvOptions {
+		self.order = order;
+		self
+	}
+}
+
+/// The error returned when CSV data cannot be read into a `Bag`.
+#[derive(Debug)]
+pub enum CsvError {
+	/// Reading from the underlying reader failed.
+	Io(io::Error),
+	/// A row could not be parsed. Rows are numbered from 1.
+	Row {
+		/// The number of the row.
+		row: usize,
+		/// A description of what is wrong with the row.
+		message: String,
+	},
+}
+
+impl fmt::Display for CsvError {
+	/// Displays the row number, if any, followed by a description of the error.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, CsvOptions};
+	/// let error = Bag::<f64>::read_csv("1.5\nabc\n".as_bytes(), &CsvOptions::new()).unwrap_err();
+	/// assert_eq!("row 2: invalid value `abc`: invalid float literal", format!("{}", error));
+	/// ```
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			CsvError::Io(ref error) => write!(f, "{}", error),
+			CsvError::Row { row, ref message } => write!(f, "row {}: {}", row, message),
+		}
+	}
+}
+
+impl Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+	fn from(error: io::Error) -> CsvError {
+		CsvError::Io(error)
+	}
+}
+
+impl<T: FromStr + Clone> Bag<T> where T::Err: fmt::Display {
+	/// Reads a `bag` from CSV data, inserting the value of each row as many times as its count.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, CsvOptions};
+	/// let x: Bag<f64> = Bag::<f64>::read_csv("1.5\n2\n1.5\n".as_bytes(), &CsvOptions::new()).unwrap();
+	/// assert_eq!(2, x.occurrences(&1.5));
+	/// 
+	/// let csv = "name;value;count\nwidth;2.5;3\nheight;4;1\n";
+	/// let options = CsvOptions::new().delimiter(';').has_header(true).value_column(1).count_column(Some(2));
+	/// let y: Bag<f64> = Bag::<f64>::read_csv(csv.as_bytes(), &options).unwrap();
+	/// assert_eq!(3, y.occurrences(&2.5));
+	/// assert_eq!(4, y.size());
+	/// 
+	/// let quoted = "\"a, b\",2\n\"say \"\"hi\"\"\nthere\",1\n";
+	/// let options = CsvOptions::new().count_column(Some(1));
+	/// let z: Bag<String> = Bag::<String>::read_csv(quoted.as_bytes(), &options).unwrap();
+	/// assert_eq!(2, z.occurrences("a, b"));
+	/// assert_eq!(1, z.occurrences("say \"hi\"\nthere"));
+	/// 
+	/// let error = Bag::<String>::read_csv("a\n\"b\nc\"\n\"d\n".as_bytes(), &CsvOptions::new()).unwrap_err();
+	/// assert_eq!("row 3: unterminated quoted field", format!("{}", error));
+	/// 
+	/// let error = Bag::<String>::read_csv("5\" screen,1\nfoo,2\n".as_bytes(), &options).unwrap_err();
+	/// assert_eq!("row 1: quote inside an unquoted field", format!("{}", error));
+	/// 
+	/// let error = Bag::<String>::read_csv("x,3000000000\n".as_bytes(), &options).unwrap_err();
+	/// assert_eq!("row 1: total count exceeds 16777216", format!("{}", error));
+	/// assert_eq!(5, Bag::<String>::read_csv("x,5\n".as_bytes(), &options.max_total_count(5)).unwrap().size());
+	/// ```
+	pub fn read_csv<R: BufRead>(mut reader: R, options: &CsvOptions) -> Result<Bag<T>, CsvError> {
+		let mut bag = Bag::<T>::new();
+		let mut header_pending = options.has_header;
+		let mut row = 0;
+		let mut total: u64 = 0;
+
+		while let Some(record) = read_record(&mut reader, options.delimiter)? {
+			row += 1;
+
+			if record.trim().is_empty() { continue }
+
+			if header_pending {
+				header_pending = false;
+				continue;
+			}
+
+			let fields = split_record(&record, options.delimiter).map_err(|message| row_error(row, message))?;
+			let value = match field(&fields, options.value_column, row)?.parse::<T>() {
+				Ok(value) => value,
+				Err(error) => return Err(row_error(row, format!("invalid value `{}`: {}", fields[options.value_column], error))),
+			};
+
+			let count = match options.count_column {
+				Some(column) => match field(&fields, column, row)?.parse::<u64>() {
+					Ok(count) => count,
+					Err(_) => return Err(row_error(row, format!("invalid count `{}`", fields[column]))),
+				},
+				None => 1,
+			};
+
+			total = match total.checked_add(count) {
+				Some(total) if total <= options.max_total_count => total,
+				_ => return Err(row_error(row, format!("total count exceeds {}", options.max_total_count))),
+			};
+
+			bag.try_insert_n(value, count).map_err(|error| row_error(row, error.to_string()))?;
+		}
+
+		Ok(bag)
+	}
+}
+
+impl<T: fmt::Display + PartialOrd> Bag<T> {
+	/// Writes each distinct element of the `bag` and the number of times it occurs as a CSV row.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, CsvOptions, CsvOrder};
+	/// let x: Bag<f64> = vec![2.5, 1.0, 2.5].into_iter().collect();
+	/// let mut csv = Vec::new();
+	/// x.write_csv(&mut csv, &CsvOptions::new().has_header(true)).unwrap();
+	/// assert_eq!("value,count\n1,1\n2.5,2\n", String::from_utf8(csv).unwrap());
+	/// 
+	/// let mut csv = Vec::new();
+	/// x.write_csv(&mut csv, &CsvOptions::new().delimiter('\t').order(CsvOrder::Count)).unwrap();
+	/// assert_eq!("2.5\t2\n1\t1\n", String::from_utf8(csv).unwrap());
+	/// 
+	/// let y: Bag<String> = vec!["a, b".to_string(), "say \"hi\"".to_string()].into_iter().collect();
+	/// let mut csv = Vec::new();
+	/// y.write_csv(&mut csv, &CsvOptions::new()).unwrap();
+	/// assert_eq!("\"a, b\",1\n\"say \"\"hi\"\"\",1\n", String::from_utf8(csv.clone()).unwrap());
+	/// assert_eq!(y, Bag::<String>::read_csv(&csv[..], &CsvOptions::new().count_column(Some(1))).unwrap());
+	/// ```
+	pub fn write_csv<W: Write>(&self, writer: &mut W, options: &CsvOptions) -> io::Result<()> {
+		let mut runs = self.sorted_runs();
+
+		if options.order == CsvOrder::Count {
+			runs.sort_by_key(|&(_, count)| Reverse(count));
+		}
+
+		if options.has_header {
+			writeln!(writer, "value{}count", options.delimiter)?;
+		}
+
+		for (datum, count) in runs {
+			writeln!(writer, "{}{}{}", quote(&datum.to_string(), options.delimiter), options.delimiter, count)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Reads the lines of one record, joining lines while a quoted field is open.
+/// Returns `None` at the end of the input.
+fn read_record<R: BufRead>(reader: &mut R, delimiter: char) -> Result<Option<String>, CsvError> {
+	let mut record = String::new();
+
+	loop {
+		let length = reader.read_line(&mut record)?;
+
+		if length == 0 {
+			if record.is_empty() { return Ok(None) }
+
+			break;
+		}
+
+		if !in_quoted_field(&record, delimiter) { break }
+	}
+
+	if record.ends_with('\n') { record.pop(); }
+
+	if record.ends_with('\r') { record.pop(); }
+
+	Ok(Some(record))
+}
+
+/// Returns true if the record ends inside a quoted field, counting only quotes that
+/// begin a field as opening one, as `split_record` does.
+fn in_quoted_field(record: &str, delimiter: char) -> bool {
+	let mut quoted = false;
+	let mut field_start = true;
+	let mut chars = record.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		if quoted {
+			if c == '"' && chars.next_if_eq(&'"').is_none() {
+				quoted = false;
+			}
+		}
+		else if c == delimiter {
+			field_start = true;
+		}
+		else if field_start && c == '"' {
+			quoted = true;
+			field_start = false;
+		}
+		else if !c.is_whitespace() {
+			field_start = false;
+		}
+	}
+
+	quoted
+}
+
+/// Splits a record into fields at the delimiter, trimming unquoted fields and
+/// removing the quotes around quoted ones.
+fn split_record(record: &str, delimiter: char) -> Result<Vec<String>, String> {
+	let blank = |c: char| c != delimiter && c.is_whitespace();
+	let mut fields = Vec::new();
+	let mut chars = record.chars().peekable();
+
+	loop {
+		while chars.peek().is_some_and(|&c| blank(c)) {
+			chars.next();
+		}
+
+		let mut field = String::new();
+
+		if chars.peek() == Some(&'"') {
+			chars.next();
+
+			loop {
+				match chars.next() {
+					Some('"') if chars.peek() == Some(&'"') => {
+						chars.next();
+						field.push('"');
+					}
+					Some('"') => break,
+					Some(c) => field.push(c),
+					None => return Err("unterminated quoted field".to_string()),
+				}
+			}
+
+			while chars.peek().is_some_and(|&c| blank(c)) {
+				chars.next();
+			}
+
+			match chars.next() {
+				Some(c) if c == delimiter => fields.push(field),
+				Some(_) => return Err("unexpected text after a quoted field".to_string()),
+				None => {
+					fields.push(field);
+					return Ok(fields);
+				}
+			}
+		}
+		else {
+			loop {
+				match chars.next() {
+					Some(c) if c == delimiter => break,
+					Some('"') => return Err("quote inside an unquoted field".to_string()),
+					Some(c) => field.push(c),
+					None => {
+						fields.push(field.trim_end().to_string());
+						return Ok(fields);
+					}
+				}
+			}
+
+			fields.push(field.trim_end().to_string());
+		}
+	}
+}
+
+/// Quotes a field if it holds the delimiter, a quote or a line break, or would
+/// otherwise lose surrounding whitespace when read back.
+fn quote(text: &str, delimiter: char) -> Cow<'_, str> {
+	let needs_quotes = text.contains([delimiter, '"', '\n', '\r'])
+		|| text.trim() != text;
+
+	if needs_quotes {
+		Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+	}
+	else {
+		Cow::Borrowed(text)
+	}
+}
+
+/// Returns the field at `column`, or an error if the row is too short.
+fn field(fields: &[String], column: usize, row: usize) -> Result<&str, CsvError> {
+	match fields.get(column) {
+		Some(field) => Ok(field),
+		None => Err(row_error(row, format!("expected at least {} columns", column + 1))),
+	}
+}
+
+/// Returns a `CsvError` for the given row.
+fn row_error(row: usize, message: String) -> CsvError {
+	CsvError::Row { row, message }
+}
@@ -0,0 +1,205 @@
+//! Configurable display of the elements of a `Bag`.
+//! 
+//! The plain `{}` layout of a bag writes every element, which is unreadable for large
+//! bags. `display_with` writes the elements between braces instead, and can stop after
+//! a number of elements with an `… and N more` tail and put each element on its own line.
+//! When the elements can be compared, the adapter can also group equal elements as
+//! `value×count` and sort them.
+//! 
+//! The `{:#}` flag means the same for a bag and for an adapter: show at most the first
+//! ten elements, or groups, and summarise the rest.
+use std::fmt;
+
+use Bag;
+
+/// The most elements `{:#}` shows before summarising the rest.
+pub(crate) const SUMMARY_LENGTH: usize = 10;
+
+/// Layout options for `Bag::display_with`.
+/// 
+/// By default every element is shown, in the bag's order, on one line.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Bag, DisplayOptions};
+/// let x: Bag<f64> = vec![3.0, 1.5, 3.0, 2.0, 4.0].into_iter().collect();
+/// let options = DisplayOptions::new().max_elements(Some(2));
+/// assert_eq!("{3, 1.5, … and 3 more}", format!("{}", x.display_with(options)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+	max_elements: Option<usize>,
+	multi_line: bool,
+}
+
+impl DisplayOptions {
+	/// Returns the default options.
+	pub fn new() -> DisplayOptions {
+		DisplayOptions::default()
+	}
+
+	/// Sets the most elements to show before summarising the rest, or `None` to show them all.
+	/// When grouping, this is the most groups to show.
+	pub fn max_elements(mut self, max_elements: Option<usize>) -> DisplayOptions {
+		self.max_elements = max_elements;
+		self
+	}
+
+	/// Sets whether each element is shown on its own line.
+	pub fn multi_line(mut self, multi_line: bool) -> DisplayOptions {
+		self.multi_line = multi_line;
+		self
+	}
+}
+
+/// Displays a `Bag` with the given `DisplayOptions`, returned by `Bag::display_with`.
+/// 
+/// Grouping and sorting compare the elements, so they are switched on here rather than
+/// in `DisplayOptions`, and only for element types that are `PartialOrd`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Bag, DisplayOptions};
+/// let x: Bag<f64> = vec![3.0, 1.5, 3.0, 2.0, 4.0].into_iter().collect();
+/// let shown = x.display_with(DisplayOptions::new().max_elements(Some(2))).grouped(true).sorted(true);
+/// assert_eq!("{1.5, 2, … and 3 more}", format!("{}", shown));
+/// ```
+#[derive(Debug)]
+pub struct DisplayWith<'a, T: 'a> {
+	bag: &'a Bag<T>,
+	options: DisplayOptions,
+	grouped: bool,
+	sorted: bool,
+	ordered: Option<OrderedEntries<'a, T>>,
+}
+
+/// Lists the entries of a bag to display, given whether to group and to sort them.
+type OrderedEntries<'a, T> = fn(&'a Bag<T>, bool, bool) -> Vec<(&'a T, u64)>;
+
+impl<'a, T: PartialOrd> DisplayWith<'a, T> {
+	/// Sets whether equal elements are shown once, as `value×count`.
+	pub fn grouped(mut self, grouped: bool) -> DisplayWith<'a, T> {
+		self.grouped = grouped;
+		self.ordered = Some(ordered_entries::<T>);
+		self
+	}
+
+	/// Sets whether elements are shown in ascending order rather than the bag's order.
+	pub fn sorted(mut self, sorted: bool) -> DisplayWith<'a, T> {
+		self.sorted = sorted;
+		self.ordered = Some(ordered_entries::<T>);
+		self
+	}
+}
+
+impl<'a, T: fmt::Display> fmt::Display for DisplayWith<'a, T> {
+	/// Displays the elements of the `bag` as the options direct.
+	/// 
+	/// With the `{:#}` flag at most ten elements, or groups, are shown.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, DisplayOptions};
+	/// let x: Bag<u32> = vec![2, 1, 2].into_iter().collect();
+	/// let shown = x.display_with(DisplayOptions::new().multi_line(true)).grouped(true);
+	/// assert_eq!("{\n    2×2,\n    1,\n}", format!("{}", shown));
+	/// 
+	/// let y: Bag<u32> = (0..25).collect();
+	/// let shown = y.display_with(DisplayOptions::new());
+	/// assert_eq!("{0, 1, 2, 3, 4, 5, 6, 7, 8, 9, … and 15 more}", format!("{:#}", shown));
+	/// ```
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let entries: Vec<(&T, u64)> = match self.ordered {
+			Some(ordered) => ordered(self.bag, self.grouped, self.sorted),
+			None => self.bag.data.iter().map(|datum| (datum, 1)).collect(),
+		};
+		let max_entries = match self.options.max_elements {
+			Some(max_entries) if f.alternate() => Some(max_entries.min(SUMMARY_LENGTH)),
+			None if f.alternate() => Some(SUMMARY_LENGTH),
+			max_entries => max_entries,
+		};
+
+		write_entries(f, &entries, max_entries, self.options.multi_line)
+	}
+}
+
+impl<T: fmt::Display> Bag<T> {
+	/// Returns an adapter that displays the elements of the `bag` with the given options.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, DisplayOptions};
+	/// let x: Bag<f64> = (0..3000).map(|i| (i % 50) as f64).collect();
+	/// let shown = x.display_with(DisplayOptions::new().max_elements(Some(3))).grouped(true).sorted(true);
+	/// assert_eq!("{0×60, 1×60, 2×60, … and 2820 more}", format!("{}", shown));
+	/// 
+	/// struct Opaque;
+	/// # impl std::fmt::Display for Opaque {
+	/// #     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { f.write_str("?") }
+	/// # }
+	/// let y: Bag<Opaque> = vec![Opaque, Opaque, Opaque].into_iter().collect();
+	/// assert_eq!("{?, … and 2 more}", format!("{}", y.display_with(DisplayOptions::new().max_elements(Some(1)))));
+	/// ```
+	pub fn display_with(&self, options: DisplayOptions) -> DisplayWith<'_, T> {
+		DisplayWith { bag: self, options, grouped: false, sorted: false, ordered: None }
+	}
+}
+
+/// Returns the entries of the `bag` to display, each element once with its count when
+/// `grouped`, and in ascending order when `sorted`.
+fn ordered_entries<T: PartialOrd>(bag: &Bag<T>, grouped: bool, sorted: bool) -> Vec<(&T, u64)> {
+	match (grouped, sorted) {
+		(true, true) => bag.sorted_runs(),
+		(true, false) => bag.grouped_runs(),
+		(false, true) => {
+			let mut entries = Vec::new();
+
+			for (datum, count) in bag.sorted_runs() {
+				for _ in 0..count {
+					entries.push((datum, 1));
+				}
+			}
+
+			entries
+		}
+		(false, false) => bag.data.iter().map(|datum| (datum, 1)).collect(),
+	}
+}
+
+/// Writes up to `max_entries` of the entries between braces, each as `value` or
+/// `value×count`, followed by the number of elements left out.
+fn write_entries<T: fmt::Display>(f: &mut fmt::Formatter, entries: &[(&T, u64)], max_entries: Option<usize>, multi_line: bool) -> fmt::Result {
+	let shown = match max_entries {
+		Some(max_entries) if max_entries < entries.len() => max_entries,
+		_ => entries.len(),
+	};
+	let hidden: u64 = entries[shown..].iter().map(|&(_, count)| count).sum();
+	let separator = if multi_line { ",\n    " } else { ", " };
+	let mut prefix = if multi_line { "\n    " } else { "" };
+
+	write!(f, "{{")?;
+
+	for &(datum, count) in &entries[..shown] {
+		write!(f, "{}{}", prefix, datum)?;
+
+		if count > 1 {
+			write!(f, "×{}", count)?;
+		}
+
+		prefix = separator;
+	}
+
+	if hidden > 0 {
+		write!(f, "{}… and {} more", prefix, hidden)?;
+	}
+
+	if multi_line && !entries.is_empty() {
+		writeln!(f, ",")?;
+	}
+
+	write!(f, "}}")
+}
@@ -0,0 +1,233 @@
+//! In-place adjustment of how many times an element occurs in a `Bag`.
+//! 
+//! `insert_n` grows the capacity once for all of the new copies, asking the growth
+//! policy for a capacity that holds them, instead of growing once per `insert`.
+//! Copies are counted and removed with `occurrences` and `erase_n`, so they match by `==`
+//! as every other query does, and the bag's shrink threshold is honoured.
+use std::fmt;
+
+use {Bag, BagError};
+
+/// A view into the copies of one element in a `Bag`, returned by `Bag::entry`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::Bag;
+/// let mut x: Bag<u32> = vec![1, 2, 2].into_iter().collect();
+/// x.entry(2).add(3);
+/// x.entry(1).remove(1);
+/// assert_eq!(5, x.occurrences(&2));
+/// assert_eq!(0, x.occurrences(&1));
+/// ```
+pub struct Entry<'a, T: 'a> {
+	bag: &'a mut Bag<T>,
+	element: T,
+}
+
+impl<'a, T: 'a + fmt::Debug> fmt::Debug for Entry<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Entry").field("element", &self.element).finish()
+	}
+}
+
+impl<'a, T: 'a + PartialEq + Clone> Entry<'a, T> {
+	/// Returns the element this entry adjusts.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = Bag::<u32>::new();
+	/// assert_eq!(&7, x.entry(7).element());
+	/// ```
+	pub fn element(&self) -> &T {
+		&self.element
+	}
+
+	/// Returns the number of times the element occurs in the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = vec![4, 4, 1].into_iter().collect();
+	/// assert_eq!(2, x.entry(4).count());
+	/// ```
+	pub fn count(&self) -> u64 {
+		self.bag.occurrences(&self.element)
+	}
+
+	/// Adds `n` copies of the element to the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = Bag::<u32>::new();
+	/// x.entry(4).add(5);
+	/// assert_eq!(5, x.size());
+	/// ```
+	pub fn add(&mut self, n: u64) -> &mut Entry<'a, T> {
+		self.bag.insert_n(self.element.clone(), n);
+		self
+	}
+
+	/// Removes up to `n` copies of the element from the bag and returns how many were removed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = vec![4, 4, 1].into_iter().collect();
+	/// assert_eq!(2, x.entry(4).remove(3));
+	/// assert_eq!(0, x.entry(9).remove(1));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn remove(&mut self, n: u64) -> u64 {
+		self.bag.erase_n(&self.element, n)
+	}
+
+	/// Adds or removes copies of the element so that it occurs exactly `n` times.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = vec![4, 4, 1].into_iter().collect();
+	/// x.entry(4).set(1);
+	/// x.entry(1).set(3);
+	/// assert_eq!(1, x.occurrences(&4));
+	/// assert_eq!(3, x.occurrences(&1));
+	/// ```
+	pub fn set(&mut self, n: u64) -> &mut Entry<'a, T> {
+		self.bag.set_count(self.element.clone(), n);
+		self
+	}
+
+	/// Removes every copy of the element from the bag and returns how many were removed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<u32> = vec![4, 4, 1].into_iter().collect();
+	/// assert_eq!(2, x.entry(4).remove_all());
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn remove_all(&mut self) -> u64 {
+		self.bag.erase(&self.element)
+	}
+}
+
+impl<T: PartialEq + Clone> Bag<T> {
+	/// Returns an `Entry` for adjusting the number of times `element` occurs in the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<String> = Bag::<String>::new();
+	/// x.entry("apple".to_string()).add(2).add(1);
+	/// assert_eq!(3, x.occurrences("apple"));
+	/// ```
+	pub fn entry(&mut self, element: T) -> Entry<'_, T> {
+		Entry { bag: self, element }
+	}
+
+	/// Adds or removes copies of `element` so that it occurs exactly `n` times.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.set_count(2.5, 4);
+	/// assert_eq!(4, x.occurrences(&2.5));
+	/// x.set_count(2.5, 1);
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn set_count(&mut self, element: T, n: u64) {
+		let current = self.occurrences(&element);
+
+		if n > current {
+			self.insert_n(element, n - current);
+		}
+		else if n < current {
+			self.erase_n(&element, current - n);
+		}
+	}
+}
+
+impl<T: Clone> Bag<T> {
+	/// Inserts `n` copies of `element` into the bag, growing the capacity at most once.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert_n(1.5, 3);
+	/// assert_eq!(3, x.size());
+	/// assert_eq!(3, x.get_capacity());
+	/// ```
+	pub fn insert_n(&mut self, element: T, n: u64) {
+		if let Err(error) = self.try_insert_n(element, n) {
+			panic!("insert_n -> {}", error);
+		}
+	}
+
+	/// Inserts `n` copies of `element` into the bag, or returns a `BagError`
+	/// if the capacity could not be grown to hold them.
+	/// 
+	/// The bag is left unchanged when an error is returned. If cloning `element` panics,
+	/// the copies made so far stay in the bag and are counted by `size`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, BagError};
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// assert_eq!(Ok(()), x.try_insert_n(1.5, 3));
+	/// assert_eq!(Err(BagError::CapacityOverflow), x.try_insert_n(1.5, u64::MAX));
+	/// assert_eq!(3, x.size());
+	/// 
+	/// use std::panic::{self, AssertUnwindSafe};
+	/// use std::sync::atomic::{AtomicUsize, Ordering};
+	/// 
+	/// static CLONES: AtomicUsize = AtomicUsize::new(0);
+	/// 
+	/// struct Fragile;
+	/// 
+	/// impl Clone for Fragile {
+	///     fn clone(&self) -> Fragile {
+	///         if CLONES.fetch_add(1, Ordering::SeqCst) == 2 { panic!("clone failed") }
+	///         Fragile
+	///     }
+	/// }
+	/// 
+	/// let mut y: Bag<Fragile> = Bag::<Fragile>::new();
+	/// assert!(panic::catch_unwind(AssertUnwindSafe(|| y.try_insert_n(Fragile, 5))).is_err());
+	/// assert_eq!(2, y.size());
+	/// assert_eq!(2, y.iter().count());
+	/// ```
+	pub fn try_insert_n(&mut self, element: T, n: u64) -> Result<(), BagError> {
+		if n == 0 { return Ok(()) }
+
+		let required = match self.size().checked_add(n) {
+			Some(required) => required,
+			None => return Err(BagError::CapacityOverflow),
+		};
+
+		self.try_grow_to(required)?;
+
+		for _ in 1..n {
+			self.data.push(element.clone());
+			self.used += 1;
+		}
+
+		self.data.push(element);
+		self.used += 1;
+		Ok(())
+	}
+}
@@ -0,0 +1,125 @@
+//! Equivalence relations that decide which elements of a `Bag` match a target.
+//! 
+//! The `_by` query and erase functions of a `Bag`, such as `occurrences_by`, take the
+//! relation to match with as an argument, while their plain counterparts use `==`.
+//! `==` never matches NaN and treats values that differ only by rounding as different,
+//! so bags of floating-point measurements usually want one of the relations below.
+//! 
+//! The relation is not stored in the bag, so `occurrences`, `==`, the algebra and the
+//! frequency views always mean exact equality, whichever relation a caller matches with.
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A relation deciding whether an element of a `Bag` matches a target.
+/// 
+/// The relation should be symmetric, but tolerances need not be transitive.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Bag, Equivalence};
+/// struct SameLength;
+/// 
+/// impl Equivalence<String> for SameLength {
+///     fn equivalent(&self, element: &String, target: &String) -> bool {
+///         element.len() == target.len()
+///     }
+/// }
+/// 
+/// let x: Bag<String> = vec!["pear".to_string(), "plum".to_string(), "fig".to_string()].into_iter().collect();
+/// assert_eq!(2, x.occurrences_by(&"kiwi".to_string(), &SameLength));
+/// ```
+pub trait Equivalence<T> {
+	/// Returns true if `element` matches `target`.
+	fn equivalent(&self, element: &T, target: &T) -> bool;
+}
+
+/// Matches floats that are identical under the IEEE 754 total order, so NaN matches NaN.
+/// 
+/// Unlike `==`, this tells `0.0` and `-0.0` apart.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Equivalence, TotalOrder};
+/// assert!(TotalOrder.equivalent(&f64::NAN, &f64::NAN));
+/// assert!(!TotalOrder.equivalent(&0.0, &-0.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TotalOrder;
+
+impl Equivalence<f64> for TotalOrder {
+	fn equivalent(&self, element: &f64, target: &f64) -> bool {
+		element.total_cmp(target) == Ordering::Equal
+	}
+}
+
+/// Matches floats whose absolute difference is at most the given tolerance.
+/// NaN matches NaN.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Epsilon, Equivalence};
+/// assert!(Epsilon(1e-9).equivalent(&(0.1 + 0.2), &0.3));
+/// assert!(!Epsilon(1e-9).equivalent(&0.1, &0.2));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Epsilon(pub f64);
+
+impl Equivalence<f64> for Epsilon {
+	fn equivalent(&self, element: &f64, target: &f64) -> bool {
+		if element.is_nan() || target.is_nan() { return element.is_nan() && target.is_nan() }
+
+		element == target || (element - target).abs() <= self.0
+	}
+}
+
+/// Matches floats that are at most the given number of representable values apart.
+/// NaN matches NaN, and `0.0` matches `-0.0`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Equivalence, Ulps};
+/// assert!(Ulps(1).equivalent(&(0.1 + 0.2), &0.3));
+/// assert!(!Ulps(1).equivalent(&1.0, &1.0001));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ulps(pub u64);
+
+impl Equivalence<f64> for Ulps {
+	fn equivalent(&self, element: &f64, target: &f64) -> bool {
+		if element.is_nan() || target.is_nan() { return element.is_nan() && target.is_nan() }
+
+		if element == target { return true }
+
+		if element.is_sign_negative() != target.is_sign_negative() { return false }
+
+		element.to_bits().abs_diff(target.to_bits()) <= self.0
+	}
+}
+
+/// Matches elements for which the wrapped closure returns true.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Equivalence, EquivalentBy};
+/// let same_word = EquivalentBy(|a: &String, b: &String| a.eq_ignore_ascii_case(b));
+/// assert!(same_word.equivalent(&"Apple".to_string(), &"APPLE".to_string()));
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct EquivalentBy<F>(pub F);
+
+impl<F> fmt::Debug for EquivalentBy<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("EquivalentBy")
+	}
+}
+
+impl<T, F: Fn(&T, &T) -> bool> Equivalence<T> for EquivalentBy<F> {
+	fn equivalent(&self, element: &T, target: &T) -> bool {
+		(self.0)(element, target)
+	}
+}
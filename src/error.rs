@@ -0,0 +1,36 @@
+//! The error type returned by the fallible `Bag` functions.
+use std::error::Error;
+use std::fmt;
+
+/// The reasons a fallible `Bag` operation can fail.
+/// 
+/// This enum derives the Clone, Copy, Debug, PartialEq and Eq traits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BagError {
+	/// A capacity of zero was requested.
+	ZeroCapacity,
+	/// The requested capacity does not fit in a `u64`, or is larger than a `Vec` can hold.
+	CapacityOverflow,
+	/// The allocator could not provide the requested memory.
+	AllocationFailure,
+}
+
+impl fmt::Display for BagError {
+	/// Displays a short description of the error.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::BagError;
+	/// assert_eq!("capacity must be > 0", format!("{}", BagError::ZeroCapacity));
+	/// ```
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BagError::ZeroCapacity => write!(f, "capacity must be > 0"),
+			BagError::CapacityOverflow => write!(f, "capacity overflow"),
+			BagError::AllocationFailure => write!(f, "memory allocation failed"),
+		}
+	}
+}
+
+impl Error for BagError {}
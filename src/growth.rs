@@ -0,0 +1,119 @@
+//! Growth policies that decide how far a `Bag` enlarges its capacity.
+//! 
+//! A `Bag` asks its policy for a new capacity whenever `insert` finds the bag full,
+//! and whenever `+=` combines two bags. `Doubling` is used unless another policy is set.
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+
+/// A strategy for choosing the new capacity of a full `Bag`.
+/// 
+/// A policy is shared between a bag and all of its clones, and is printed by the bag's
+/// `Debug` output. The supertraits let a `Bag` holding any policy still be sent between
+/// threads and passed to `catch_unwind`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Bag, FixedIncrement};
+/// use std::panic;
+/// 
+/// let mut x: Bag<f64> = Bag::<f64>::new();
+/// x.set_growth_policy(FixedIncrement(4));
+/// assert_eq!(0, panic::catch_unwind(|| x.size()).unwrap());
+/// ```
+pub trait GrowthPolicy: fmt::Debug + Send + Sync + RefUnwindSafe + UnwindSafe {
+	/// Returns the capacity to grow to when `current` cannot hold `required` elements,
+	/// or `None` if the new capacity would overflow.
+	/// 
+	/// The bag uses `required` instead if the returned capacity is smaller.
+	fn grow(&self, current: u64, required: u64) -> Option<u64>;
+
+	/// Returns the capacity of a bag after `+=` adds a bag of capacity `addend` to one of
+	/// capacity `current`, when the result must hold `required` elements.
+	/// 
+	/// By default the two capacities are summed.
+	fn merge(&self, current: u64, addend: u64, required: u64) -> Option<u64> {
+		let _ = required;
+		current.checked_add(addend)
+	}
+}
+
+/// Doubles the capacity each time the bag fills up.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{Doubling, GrowthPolicy};
+/// assert_eq!(Some(16), Doubling.grow(8, 9));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+	fn grow(&self, current: u64, _required: u64) -> Option<u64> {
+		if current == 0 { return Some(1) }
+
+		current.checked_mul(2)
+	}
+}
+
+/// Grows the capacity by half each time the bag fills up.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{GrowthPolicy, OneAndAHalf};
+/// assert_eq!(Some(12), OneAndAHalf.grow(8, 9));
+/// assert_eq!(Some(2), OneAndAHalf.grow(1, 2));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OneAndAHalf;
+
+impl GrowthPolicy for OneAndAHalf {
+	fn grow(&self, current: u64, _required: u64) -> Option<u64> {
+		let increment = if current < 2 { 1 } else { current / 2 };
+
+		current.checked_add(increment)
+	}
+}
+
+/// Grows the capacity by a fixed number of elements each time the bag fills up.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{FixedIncrement, GrowthPolicy};
+/// assert_eq!(Some(108), FixedIncrement(100).grow(8, 9));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FixedIncrement(pub u64);
+
+impl GrowthPolicy for FixedIncrement {
+	fn grow(&self, current: u64, _required: u64) -> Option<u64> {
+		current.checked_add(self.0)
+	}
+}
+
+/// Grows the capacity only as far as is needed to hold the required elements.
+/// 
+/// Combining bags with `+=` also keeps only the capacity the result needs.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::{ExactFit, GrowthPolicy};
+/// assert_eq!(Some(9), ExactFit.grow(8, 9));
+/// assert_eq!(Some(8), ExactFit.merge(8, 4, 6));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExactFit;
+
+impl GrowthPolicy for ExactFit {
+	fn grow(&self, _current: u64, required: u64) -> Option<u64> {
+		Some(required)
+	}
+
+	fn merge(&self, current: u64, _addend: u64, required: u64) -> Option<u64> {
+		Some(if current > required { current } else { required })
+	}
+}
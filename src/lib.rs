@@ -5,167 +5,292 @@
 //!   1. `data` is a Vector of values.
 //!   2. `capacity` is an integer that determines how much space is in the `data` vector.
 //!   3. `used` is an integer that determines how much of the bag's capacity is being used.
+#[cfg(feature = "serde")]
+extern crate serde;
+
 use std::ops::{Add, AddAssign};
-use std::rc::{self, Rc};
 use std::fmt;
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::mem;
+use std::slice;
+use std::sync::Arc;
+use std::vec;
+
+mod algebra;
+mod binary;
+mod combinators;
+mod counts;
+mod counted_bag;
+mod csv;
+mod display;
+mod entry;
+mod equivalence;
+mod error;
+mod growth;
+mod ordered_bag;
+mod parse;
+#[cfg(feature = "serde")]
+mod serialization;
+mod statistics;
+
+pub use binary::{Decode, Encode};
+pub use counted_bag::CountedBag;
+pub use csv::{CsvError, CsvOptions, CsvOrder};
+pub use display::{DisplayOptions, DisplayWith};
+pub use entry::Entry;
+pub use equivalence::{Epsilon, Equivalence, EquivalentBy, TotalOrder, Ulps};
+pub use error::BagError;
+pub use growth::{Doubling, ExactFit, FixedIncrement, GrowthPolicy, OneAndAHalf};
+pub use ordered_bag::OrderedBag;
+pub use parse::ParseBagError;
+#[cfg(feature = "serde")]
+pub use serialization::as_counts;
+pub use statistics::{Numeric, Statistics};
+
+/// The largest total number of elements a bag read from text or serialized data may hold,
+/// unless a `_with_limit` function allows another. A few bytes of input can ask for
+/// billions of copies of one element, so readers refuse such input instead of allocating it.
+pub const MAX_TOTAL_COUNT: u64 = 1 << 24;
 
 /// The struct for the `Bag` data type.
 /// 
-/// This struct derives the Default, Clone, Debug, and Hash traits.
+/// This struct derives the Debug trait. Its Default is an empty bag with a capacity of 1, as `new` returns.
 /// 
 /// This struct also uses C representation to deal with memory management of the data.
-#[derive(Default, Clone, Debug, Hash)]
+/// 
+/// Each operation only asks of `T` what it actually uses: construction, `insert`,
+/// `size` and capacity management need nothing, `occurrences` and the erase functions
+/// need `PartialEq`, displaying needs `Display` and cloning needs `Clone`.
+/// 
+/// The order of the elements is not part of a bag's contents: the erase functions
+/// may move the remaining elements to avoid shifting them.
+/// 
+/// Capacity grows according to a `GrowthPolicy`, which is `Doubling` unless
+/// `set_growth_policy` chooses another. A bag can also shrink itself automatically
+/// after erasing, once `set_shrink_threshold` has been given a fraction of its capacity.
+/// 
+/// The query and erase functions compare elements with `==`. Their `_by` counterparts,
+/// such as `occurrences_by`, match elements with an `Equivalence` passed to them instead,
+/// such as a tolerance for floating-point values.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use rust_adt::Bag;
+/// let mut x: Bag<String> = Bag::<String>::new();
+/// x.insert("apple".to_string());
+/// x.insert("pear".to_string());
+/// assert_eq!(1, x.occurrences("apple"));
+/// ```
+#[derive(Debug)]
 #[repr(C)]
 pub struct Bag<T> {
 	data: Vec<T>,
 	capacity: u64,
 	used: u64,
+	growth: Option<Arc<dyn GrowthPolicy>>,
+	shrink_below: Option<f64>,
 }
 
-impl<T: PartialEq> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
-	/// Checks that two `bag` data structures are equal.
+impl<T> Default for Bag<T> {
+	/// Returns a new `Bag` with a capacity of 1, as `Bag::new` does.
 	/// 
-	/// This function bases equality on the following:
+	/// # Examples
 	/// 
-	///   1. Each `bag` has the same capacity.
-	///   2. Each `bag` has the same number of used elements.
-	///   3. Each element of each `bag` is equal in value and position.
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = Bag::<f64>::default();
+	/// assert_eq!(1, x.get_capacity());
+	/// assert_eq!(0, x.size());
+	/// ```
+	fn default() -> Bag<T> {
+		Bag::<T>::new()
+	}
+}
+
+impl<T: Clone> Clone for Bag<T> {
+	/// Clones an entire `bag` structure.
+	/// 
+	/// The clone reserves the same capacity as the original, so `get_capacity()`
+	/// keeps describing the memory it actually holds.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
-	/// let y: Bag<T> = Bag::<T>::new();
-	/// assert_eq!(true, x.eq(y));
-	/// x.insert(1);
-	/// assert_eq!(false, x.eq(y));
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(8);
+	/// x.insert(1.0);
+	/// let y: Bag<f64> = x.clone();
+	/// assert_eq!(x, y);
+	/// assert_eq!(8, y.get_capacity());
+	/// assert!(y.get_data().capacity() >= 8);
 	/// ```
-	pub fn eq(&self, comparand: &Bag<T>) -> bool {
-		let mut is_equal = true;
+	fn clone(&self) -> Bag<T> {
+		Bag::<T>::new_from_bag(self)
+	}
+}
 
-		if self.get_capacity() != comparand.get_capacity() { is_equal = false }
+impl<T: PartialEq> PartialEq for Bag<T> {
+	/// Checks that two `bag` data structures are equal as multisets.
+	/// 
+	/// This function bases equality on the following:
+	/// 
+	///   1. Each `bag` has the same number of used elements.
+	///   2. Every element occurs the same number of times in each `bag`.
+	/// 
+	/// Neither the order of the elements nor the capacity of either `bag` is considered.
+	/// Use `structurally_eq()` to compare the layout of two bags as well.
+	/// 
+	/// Inequality (`!=`) is the negation of this function.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(2);
+	/// let mut y: Bag<f64> = Bag::<f64>::new_with_capacity(8);
+	/// assert!(x == y);
+	/// x.insert(1.0);
+	/// assert!(x != y);
+	/// x.insert(2.0);
+	/// y.insert(2.0);
+	/// y.insert(1.0);
+	/// assert!(x == y);
+	/// ```
+	fn eq(&self, comparand: &Bag<T>) -> bool {
+		if self.size() != comparand.size() { return false }
 
-		if self.size() != comparand.size() { is_equal = false }
+		let mut matched = vec![false; comparand.data.len()];
 
-		let mut index = 0;
+		for datum in &self.data {
+			let mut index = 0;
 
-		while is_equal && index < self.size() {
-			if self.data[index as usize] != comparand.data[index as usize] {
-				is_equal = false;
-			}
-			else {
+			while index < comparand.data.len() && (matched[index] || comparand.data[index] != *datum) {
 				index += 1;
 			}
+
+			if index == comparand.data.len() { return false }
+
+			matched[index] = true;
 		}
 
-		is_equal
+		true
 	}
+}
 
-	/// Checks that two `bag` data structures are NOT equal.
+impl<T: Eq> Eq for Bag<T> {}
+
+impl<T: Hash> Hash for Bag<T> {
+	/// Hashes the `bag` independently of the order of its elements, so that bags
+	/// which are equal as multisets hash identically.
 	/// 
-	/// This function negates the `eq()` function in order to determine inequality.
+	/// Each element is hashed on its own and the results are combined with a
+	/// wrapping sum before being fed to `state` along with the used count.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let x: Bag<T> = Bag::<T>::new();
-	/// let mut y: Bag<T> = Bag::<T>::new();
-	/// assert_eq!(false, x.ne(y));
+	/// # use rust_adt::Bag;
+	/// use std::collections::HashSet;
+	/// 
+	/// let mut x: Bag<u32> = Bag::<u32>::new();
+	/// let mut y: Bag<u32> = Bag::<u32>::new_with_capacity(4);
+	/// x.insert(1);
+	/// x.insert(2);
+	/// y.insert(2);
 	/// y.insert(1);
-	/// assert_eq!(true, x.ne(y));
+	/// 
+	/// let mut set = HashSet::new();
+	/// set.insert(x);
+	/// assert!(set.contains(&y));
 	/// ```
-	pub fn ne(&self, comparand: &Bag<T>) -> bool {
-		return !(self.eq(comparand))
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		let mut combined: u64 = 0;
+
+		for datum in &self.data {
+			let mut hasher = DefaultHasher::new();
+			datum.hash(&mut hasher);
+			combined = combined.wrapping_add(hasher.finish());
+		}
+
+		self.used.hash(state);
+		combined.hash(state);
 	}
 }
 
-impl<T: fmt::Display> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
+impl<T: fmt::Display> fmt::Display for Bag<T> {
 	/// Displays the `bag` data structure.
 	/// 
 	/// This function displays the data, followed by capacity, followed by the number of used elements.
+	/// With the `{:#}` flag the data stops after the first ten elements, followed by the
+	/// number of elements left out; `display_with` offers more control.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
-	/// x.insert(1.0);
-	/// x.insert(2.0);
-	/// println!("{}", x);
-	/// // Prints the following:
-	/// // x Bag { data: [1.0, 2.0], capacity: 2, used: 2 }
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.5);
+	/// x.insert(2.5);
+	/// assert_eq!("Bag\ndata: 1.5, 2.5\ncapacity: 2\nused: 2", format!("{}", x));
+	/// 
+	/// let y: Bag<u32> = (0..25).collect();
+	/// assert_eq!("Bag\ndata: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, … and 15 more\ncapacity: 32\nused: 25", format!("{:#}", y));
 	/// ```
-	pub fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let shown = if f.alternate() { self.data.len().min(display::SUMMARY_LENGTH) } else { self.data.len() };
 		let mut str = "";
-		writeln!(f, "Bag");
-		write!(f, "data: ");
+		writeln!(f, "Bag")?;
+		write!(f, "data: ")?;
 
-		for datum in &self.data {
-			write!(f, "{}", str);
-			write!(f, "{}", datum);
+		for datum in &self.data[..shown] {
+			write!(f, "{}", str)?;
+			write!(f, "{}", datum)?;
 			str = ", ";
 		}
 
-		write!(f, "\ncapacity: {}", self.get_capacity());
+		if shown < self.data.len() {
+			write!(f, "{}… and {} more", str, self.data.len() - shown)?;
+		}
 
-		write!(f, "\nused: {}", self.size());
-		Ok(())
-	}
-}
+		write!(f, "\ncapacity: {}", self.get_capacity())?;
 
-impl<T: Clone> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
-	/// Clones an entire `bag` structure.
-	/// 
-	/// This function creates a new `bag` from the `bag` passed as an argument.
-	/// 
-	/// # Examples
-	/// 
-	/// ```
-	/// let x: Bag<T> = Bag::<T>::new();
-	/// let y: Bag<T> = x.clone();
-	/// assert_eq!(x, y);
-	/// ```
-	pub fn clone(&self) -> Bag<T> {
-		let bag = Bag::<T>::new_from_bag(&self);
-		bag
+		write!(f, "\nused: {}", self.size())
 	}
 }
 
-impl<T: AddAssign> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
+impl<T> AddAssign<Bag<T>> for Bag<T> {
 	/// Performs the `+=` operation on a `bag` using the `bag` passed as an argument.
 	/// 
 	/// Adds the argument to the `bag` calling the function.
+	/// The resulting capacity is the sum of both capacities.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(2);
-	/// let mut y: Bag<T> = Bag::<T>::new_with_capacity(3);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(2);
+	/// let mut y: Bag<f64> = Bag::<f64>::new_with_capacity(3);
 	/// x.insert(1.0);
 	/// y.insert(2.0);
-	/// x.add_assign(y);
+	/// x += y;
 	/// assert_eq!(5, x.get_capacity());
 	/// assert_eq!(2, x.size());
-	/// assert_eq!([1.0, 2.0], x.get_data());
+	/// assert_eq!(&[1.0, 2.0], &x.get_data()[..]);
 	/// ```
-	pub fn add_assign(&mut self, mut addend: Bag<T>) {
-		let mut current_data = self.data.clone();
-		let current_used = self.size();
-
-		if self.size() + addend.size() > self.get_capacity() {
-			self.ensure_capacity(current_used + addend.size());
+	fn add_assign(&mut self, addend: Bag<T>) {
+		if let Err((_, error)) = self.try_add_assign(addend) {
+			panic!("add_assign -> {}", error);
 		}
-
-		current_data.append(&mut addend.data);
-
-		*self = Bag::<T> {
-			data: current_data,
-			capacity: self.get_capacity() + addend.get_capacity(),
-			used: self.size() + addend.size(),
-		};
 	}
 }
 
-impl<T: Add> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
+impl<T> Add<Bag<T>> for Bag<T> {
+	type Output = Bag<T>;
+
 	/// Adds two `bag` data structures together.
 	/// 
 	/// Returns a new `bag` data structure formed by adding the two `bag` data structures.
@@ -173,295 +298,1141 @@ impl<T: Add> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(2);
-	/// let mut y: Bag<T> = Bag::<T>::new_with_capacity(3);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(2);
+	/// let mut y: Bag<f64> = Bag::<f64>::new_with_capacity(3);
 	/// x.insert(1.0);
 	/// y.insert(2.0);
-	/// let mut z: Bag<T> = x.add(y);
-	/// assert_eq!(5, x.get_capacity());
-	/// assert_eq!(2, x.size());
-	/// assert_eq!([1.0, 2.0], x.get_data());
+	/// let z: Bag<f64> = x + y;
+	/// assert_eq!(5, z.get_capacity());
+	/// assert_eq!(2, z.size());
+	/// assert_eq!(&[1.0, 2.0], &z.get_data()[..]);
 	/// ```
-	pub fn add(self, addend: Bag<T>) -> Bag<T> {
-		let mut new_bag = Bag::<T>::new_with_capacity(self.get_capacity() + addend.get_capacity());
-		new_bag.add_assign(self);
-		new_bag.add_assign(addend);
+	fn add(self, addend: Bag<T>) -> Bag<T> {
+		let mut new_bag = self;
+		new_bag += addend;
 
 		new_bag
 	}
 }
 
-impl<T> Bag<T> where T:Clone + PartialEq + Add + AddAssign + fmt::Display {
-	/// Returns a new `Bag` with a capacity of 1 and no data elements being used.
+impl<'a, T: Clone> Add<&'a Bag<T>> for &'a Bag<T> {
+	type Output = Bag<T>;
+
+	/// Adds two borrowed `bag` data structures together.
+	/// 
+	/// Both operands are left untouched; the result is built from clones of their data.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let x: Bag<T> = Bag::<T>::new();
-	/// assert_eq!(x.data.len(), 1);
-	/// assert_eq!(x.used, 0);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(2);
+	/// let mut y: Bag<f64> = Bag::<f64>::new_with_capacity(3);
+	/// x.insert(1.0);
+	/// y.insert(2.0);
+	/// let z: Bag<f64> = &x + &y;
+	/// assert_eq!(5, z.get_capacity());
+	/// assert_eq!(1, x.size());
+	/// assert_eq!(1, y.size());
 	/// ```
-	pub fn new() -> Bag<T> {
-		Bag::<T> {
-			data: Vec::with_capacity(1),
-			capacity: 1,
-			used: 0,
-		}
+	fn add(self, addend: &'a Bag<T>) -> Bag<T> {
+		self.clone() + addend.clone()
 	}
+}
 
-	/// Returns a new `Bag` with the given capacity and no data elements being used.
+impl<T> Extend<T> for Bag<T> {
+	/// Inserts every element of the iterator into the `bag`.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let x: Bag<T> = Bag::<T>::new_with_capacity(10);
-	/// assert_eq!(x.data.len(), 0);
-	/// assert_eq!(x.used, 0);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.extend(vec![1.0, 2.0, 2.0]);
+	/// assert_eq!(3, x.size());
+	/// assert_eq!(4, x.get_capacity());
 	/// ```
-	pub fn new_with_capacity(initial_capacity: u64) -> Bag<T> {
-		if initial_capacity < 1 {
-			panic!("new_with_capacity() -> initial_capacity must be > 0");
-		}
-
-		// Needs to divide initial_capacity by 2 if memory is exceeded
-		Bag::<T> {
-			data: Vec::with_capacity(initial_capacity as usize),
-			capacity: initial_capacity,
-			used: 0,
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		for entry in iter {
+			self.insert(entry);
 		}
 	}
+}
 
-	/// Returns a new `Bag` with the given capacity and no data elements being used.
+impl<'a, T: 'a + Copy> Extend<&'a T> for Bag<T> {
+	/// Inserts a copy of every element of the iterator into the `bag`.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// // Create an original bag.
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(3);
-	/// x.data.push(1.0);
-	/// x.data.push(2.0);
-	/// x.data.push(3.0);
-	/// 
-	/// // Create the new bag from the original bag.
-	/// let y: Bag<T> = Bag::<T>::new_from_bag(&x);
-	/// assert_eq!(x.data.len(), y.data.len());
-	/// assert_eq!(x.used, y.used);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.extend(&[1.0, 2.0]);
+	/// assert_eq!(2, x.size());
 	/// ```
-	pub fn new_from_bag(source: &Bag<T>) -> Bag<T> {
-		// Needs to check if source is null
-		let mut dest = Vec::<T>::with_capacity(source.get_capacity() as usize);
-		let mut temp: Rc<&Vec<T>> = Rc::new(source.get_data());
-		let mut to_data = &**Rc::get_mut(&mut temp).unwrap();
-
-		Bag::<T> {
-			data: to_data.clone(),
-			capacity: source.get_capacity(),
-			used: source.size(),
+	fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+		for entry in iter {
+			self.insert(*entry);
 		}
 	}
+}
 
-	/// Returns the vector of data stored in the bag.
+impl<T> FromIterator<T> for Bag<T> {
+	/// Builds a new `bag` by inserting every element of the iterator.
+	/// 
+	/// The capacity grows exactly as it would through repeated calls to `insert`.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
-	/// x.insert(1);
-	/// assert_eq!(1, x.get_data()[0]);
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.0, 2.0, 3.0].into_iter().collect();
+	/// assert_eq!(3, x.size());
+	/// assert_eq!(4, x.get_capacity());
 	/// ```
-	pub fn get_data(&self) -> &Vec<T> {
-		&self.data
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Bag<T> {
+		let mut bag = Bag::<T>::new();
+		bag.extend(iter);
+
+		bag
 	}
+}
 
-	/// Returns the capacity of `data` as an integer.
+impl<T> IntoIterator for Bag<T> {
+	type Item = T;
+	type IntoIter = vec::IntoIter<T>;
+
+	/// Consumes the `bag`, yielding each element it held.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(1);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
 	/// x.insert(1.0);
 	/// x.insert(2.0);
-	/// assert_eq!(2, x.get_capacity());
+	/// let values: Vec<f64> = x.into_iter().collect();
+	/// assert_eq!(vec![1.0, 2.0], values);
 	/// ```
-	pub fn get_capacity(&self) -> u64 {
-		self.capacity
+	fn into_iter(self) -> vec::IntoIter<T> {
+		self.data.into_iter()
 	}
+}
 
-	/// Returns the number of elements being used in `data`.
-	/// 
-	/// # Examples
-	/// 
-	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(5);
-	/// x.insert(1.0);
-	/// x.insert(2.0);
-	/// assert_eq!(2, x.size());
-	/// ```
-	pub fn size(&self) -> u64 {
-		self.used
+impl<'a, T> IntoIterator for &'a Bag<T> {
+	type Item = &'a T;
+	type IntoIter = slice::Iter<'a, T>;
+
+	fn into_iter(self) -> slice::Iter<'a, T> {
+		self.iter()
 	}
+}
 
-	/// Inserts an element into the `data` list in the bag.
-	/// Checks to ensure the capacity can handle the new entry.
+impl<'a, T> IntoIterator for &'a mut Bag<T> {
+	type Item = &'a mut T;
+	type IntoIter = slice::IterMut<'a, T>;
+
+	fn into_iter(self) -> slice::IterMut<'a, T> {
+		self.iter_mut()
+	}
+}
+
+impl<T: PartialEq> Bag<T> {
+	/// Checks that two `bag` data structures have the same layout.
+	/// 
+	/// This function bases equality on the following:
+	/// 
+	///   1. Each `bag` has the same capacity.
+	///   2. Each `bag` has the same number of used elements.
+	///   3. Each element of each `bag` is equal in value and position.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// let mut y: Bag<f64> = Bag::<f64>::new();
 	/// x.insert(1.0);
 	/// x.insert(2.0);
-	/// assert_eq!(2, x.size());
+	/// y.insert(2.0);
+	/// y.insert(1.0);
+	/// assert!(x == y);
+	/// assert!(!x.structurally_eq(&y));
 	/// ```
-	pub fn insert(&mut self, entry: T) {
-		let current_capacity = self.get_capacity();
+	pub fn structurally_eq(&self, comparand: &Bag<T>) -> bool {
+		let mut is_equal = true;
+
+		if self.get_capacity() != comparand.get_capacity() { is_equal = false }
 
-		if self.size() == current_capacity {
-			self.ensure_capacity(current_capacity * 2);
+		if self.size() != comparand.size() { is_equal = false }
+
+		let mut index = 0;
+
+		while is_equal && index < self.size() {
+			if self.data[index as usize] != comparand.data[index as usize] {
+				is_equal = false;
+			}
+			else {
+				index += 1;
+			}
 		}
 
-		self.data.push(entry);
-		self.used += 1;
+		is_equal
 	}
 
 	/// Determines the number of occurrences of the target element passed as an argument.
 	/// 
+	/// The target may be any borrowed form of the element type, as with `HashMap::get`,
+	/// so a `Bag<String>` can be searched with a `&str` without allocating.
+	/// 
+	/// This function and the other borrowed-target queries and erases compare with `==`.
+	/// Their `_by` counterparts, such as `occurrences_by`, take an element and the
+	/// `Equivalence` to match it with instead.
+	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
-	/// x.insert(15);
-	/// x.insert(5);
-	/// x.insert(15);
-	/// assert_eq!(2, x.occurrences(15));
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(15.0);
+	/// x.insert(5.0);
+	/// x.insert(15.0);
+	/// assert_eq!(2, x.occurrences(&15.0));
+	/// 
+	/// let y: Bag<Box<str>> = vec!["apple".into(), "pear".into()].into_iter().collect();
+	/// assert_eq!(1, y.occurrences("apple"));
 	/// ```
-	pub fn occurrences(&self, target: T) -> u64 {
-		let mut answer = 0;
-
-		for i in 0..self.size() {
-			if target == self.data[i as usize] {
-				answer += 1;
-			}
-		}
-
-		answer
+	pub fn occurrences<Q: ?Sized + PartialEq>(&self, target: &Q) -> u64 where T: Borrow<Q> {
+		self.count_if(|datum| datum.borrow() == target)
 	}
 
-	/// Determines whether a new_capacity is safe to use.
-	/// If the new_capacity is safe to use, then a new bag is
-	/// created, cloning the data from the old bag into the new bag.
+	/// Returns true if the bag holds at least one element equal to the target.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
-	/// x.insert(5.0);
-	/// x.ensureCapacity(x.get_capacity + 1);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<String> = Bag::<String>::new();
+	/// x.insert("apple".to_string());
+	/// assert!(x.contains("apple"));
+	/// assert!(!x.contains("pear"));
 	/// ```
-	pub fn ensure_capacity(&mut self, new_capacity: u64) {
-		if new_capacity < 1 {
-			panic!("ensure_capacity -> new_capacity must be > 0");
-		}
-
-		if self.capacity < new_capacity {
-			let mut x = Vec::with_capacity(new_capacity as usize);
-			x = self.data.clone();
-			drop(&self.data);
-			self.data = x;
-			self.capacity = new_capacity;
-		}
+	pub fn contains<Q: ?Sized + PartialEq>(&self, target: &Q) -> bool where T: Borrow<Q> {
+		self.data.iter().any(|datum| datum.borrow() == target)
 	}
 
 	/// Returns the number of erased elements matching the given target.
 	/// 
+	/// All matches are removed in a single compaction pass, so this runs in O(n) time
+	/// for a bag of n elements no matter how many copies of the target it holds.
+	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
 	/// x.insert(1.0);
 	/// x.insert(1.0);
 	/// x.insert(1.0);
 	/// x.insert(2.0);
-	/// let count = x.erase(1.0);
+	/// let count = x.erase(&1.0);
 	/// assert_eq!(3, count);
 	/// assert_eq!(1, x.size());
 	/// ```
-	pub fn erase(&mut self, target: T) -> u64 {
-		let mut index = 0;
-		let mut number_removed = 0;
-
-		while index < self.size() {
-			if self.data[index as usize] == target {
-				self.used -= 1;
-				self.data.remove(index as usize);
-				number_removed += 1;
-			}
-			else {
-				index += 1;
-			}
-		}
+	pub fn erase<Q: ?Sized + PartialEq>(&mut self, target: &Q) -> u64 where T: Borrow<Q> {
+		self.erase_n(target, u64::MAX)
+	}
 
-		number_removed
+	/// Erases up to `n` elements matching the given target and returns the number erased.
+	/// 
+	/// Like `erase`, this makes a single compaction pass and runs in O(n) time for a bag of n elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// x.insert(1.0);
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(2, x.erase_n(&1.0, 2));
+	/// assert_eq!(1, x.occurrences(&1.0));
+	/// assert_eq!(1, x.erase_n(&1.0, 5));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase_n<Q: ?Sized + PartialEq>(&mut self, target: &Q, n: u64) -> u64 where T: Borrow<Q> {
+		self.erase_matching(|datum| datum.borrow() == target, n)
 	}
 
 	/// Returns a boolean indicating whether or not the target was found and erased.
 	/// 
+	/// Finding the target takes O(n) time, but removing it takes O(1): the last element
+	/// of the bag is moved into the freed slot, since a bag promises no order.
+	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new();
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
 	/// x.insert(1.0);
 	/// x.insert(2.0);
-	/// let mut found = x.erase_one(1.0);
+	/// let mut found = x.erase_one(&1.0);
 	/// assert_eq!(true, found);
 	/// assert_eq!(1, x.size());
-	/// found = x.erase_one(5);
+	/// found = x.erase_one(&5.0);
 	/// assert_eq!(false, found);
 	/// assert_eq!(1, x.size());
 	/// ```
-	pub fn erase_one(&mut self, target: T) -> bool {
-		let mut index = 0;
-		
-		while index < self.size() && self.data[index as usize].clone() != target {
-			index += 1;
-		}
-
-		if index == self.size() { return false }
+	pub fn erase_one<Q: ?Sized + PartialEq>(&mut self, target: &Q) -> bool where T: Borrow<Q> {
+		self.take_one(target).is_some()
+	}
 
-		self.used -= 1;
-		self.data.remove(index as usize);
-		true
+	/// Removes one element matching the given target and returns it, or `None` if there is none.
+	/// 
+	/// Like `erase_one`, this finds the target in O(n) time and removes it in O(1).
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<String> = Bag::<String>::new();
+	/// x.insert("job".to_string());
+	/// assert_eq!(Some("job".to_string()), x.take_one("job"));
+	/// assert_eq!(None, x.take_one("job"));
+	/// assert_eq!(0, x.size());
+	/// 
+	/// #[derive(Debug, PartialEq)]
+	/// struct Handle(u32);
+	/// 
+	/// let mut handles: Bag<Handle> = Bag::<Handle>::new();
+	/// handles.insert(Handle(3));
+	/// assert_eq!(Some(Handle(3)), handles.take_one(&Handle(3)));
+	/// ```
+	pub fn take_one<Q: ?Sized + PartialEq>(&mut self, target: &Q) -> Option<T> where T: Borrow<Q> {
+		self.take_first(|datum| datum.borrow() == target)
 	}
 
-	/// Truncates the capacity of the `bag` so that `capacity` matches `used`.
+	/// Removes every element matching the given target and returns them.
 	/// 
 	/// # Examples
 	/// 
 	/// ```
-	/// let mut x: Bag<T> = Bag::<T>::new_with_capacity(4);
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
 	/// x.insert(1.0);
-	/// assert_eq!(4, x.get_capacity());
-	/// assert_eq!(1, x.size());
-	/// x.trim_to_size();
-	/// assert_eq!(1, x.get_capacity());
+	/// x.insert(2.0);
+	/// x.insert(1.0);
+	/// assert_eq!(vec![1.0, 1.0], x.take_all(&1.0));
 	/// assert_eq!(1, x.size());
 	/// ```
-	pub fn trim_to_size(&mut self) {
-		if self.size() < self.get_capacity() {
-			let mut new_capacity = 0;
-
-			if self.size() <= 1 {
-				new_capacity = 1;
-			}
-			else {
-				new_capacity = self.size();
-			}
-
-			self.capacity = new_capacity;
-			let mut trimmed_array = Vec::with_capacity(new_capacity as usize);
-			trimmed_array = self.data.clone();
-			drop(&self.data);
-			self.data = trimmed_array;
-			self.capacity = new_capacity;
-		}
+	pub fn take_all<Q: ?Sized + PartialEq>(&mut self, target: &Q) -> Vec<T> where T: Borrow<Q> {
+		self.take_matching(|datum| datum.borrow() == target)
 	}
-}
\ No newline at end of file
+}
+
+impl<T> Bag<T> {
+	/// Determines the number of elements matching the target under the given equivalence.
+	/// 
+	/// The element type need not be `PartialEq`, since only the equivalence compares elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, EquivalentBy, TotalOrder};
+	/// let x: Bag<f64> = vec![f64::NAN, 1.0, f64::NAN].into_iter().collect();
+	/// assert_eq!(2, x.occurrences_by(&f64::NAN, &TotalOrder));
+	/// assert_eq!(0, x.occurrences(&f64::NAN));
+	/// 
+	/// struct Reading { sensor: u32 }
+	/// 
+	/// let readings: Bag<Reading> = vec![Reading { sensor: 1 }, Reading { sensor: 1 }].into_iter().collect();
+	/// let same_sensor = EquivalentBy(|a: &Reading, b: &Reading| a.sensor == b.sensor);
+	/// assert_eq!(2, readings.occurrences_by(&Reading { sensor: 1 }, &same_sensor));
+	/// ```
+	pub fn occurrences_by<E: Equivalence<T> + ?Sized>(&self, target: &T, equivalence: &E) -> u64 {
+		self.count_if(|datum| equivalence.equivalent(datum, target))
+	}
+
+	/// Returns true if the bag holds at least one element matching the target under the
+	/// given equivalence.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Epsilon};
+	/// let x: Bag<f64> = vec![0.1 + 0.2].into_iter().collect();
+	/// assert!(x.contains_by(&0.3, &Epsilon(1e-9)));
+	/// assert!(!x.contains(&0.3));
+	/// ```
+	pub fn contains_by<E: Equivalence<T> + ?Sized>(&self, target: &T, equivalence: &E) -> bool {
+		self.data.iter().any(|datum| equivalence.equivalent(datum, target))
+	}
+
+	/// Erases every element matching the target under the given equivalence and returns
+	/// the number erased.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Epsilon};
+	/// let mut x: Bag<f64> = vec![0.1 + 0.2, 0.3, 0.5].into_iter().collect();
+	/// assert_eq!(2, x.erase_by(&0.3, &Epsilon(1e-9)));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase_by<E: Equivalence<T> + ?Sized>(&mut self, target: &T, equivalence: &E) -> u64 {
+		self.erase_n_by(target, u64::MAX, equivalence)
+	}
+
+	/// Erases up to `n` elements matching the target under the given equivalence and returns
+	/// the number erased.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, TotalOrder};
+	/// let mut x: Bag<f64> = vec![f64::NAN, f64::NAN, f64::NAN].into_iter().collect();
+	/// assert_eq!(2, x.erase_n_by(&f64::NAN, 2, &TotalOrder));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase_n_by<E: Equivalence<T> + ?Sized>(&mut self, target: &T, n: u64, equivalence: &E) -> u64 {
+		self.erase_matching(|datum| equivalence.equivalent(datum, target), n)
+	}
+
+	/// Returns a boolean indicating whether or not an element matching the target under the
+	/// given equivalence was found and erased.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, TotalOrder};
+	/// let mut x: Bag<f64> = vec![f64::NAN].into_iter().collect();
+	/// assert!(x.erase_one_by(&f64::NAN, &TotalOrder));
+	/// assert_eq!(0, x.size());
+	/// ```
+	pub fn erase_one_by<E: Equivalence<T> + ?Sized>(&mut self, target: &T, equivalence: &E) -> bool {
+		self.take_one_by(target, equivalence).is_some()
+	}
+
+	/// Removes one element matching the target under the given equivalence and returns it,
+	/// or `None` if there is none.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Epsilon};
+	/// let mut x: Bag<f64> = vec![0.1 + 0.2].into_iter().collect();
+	/// assert_eq!(Some(0.1 + 0.2), x.take_one_by(&0.3, &Epsilon(1e-9)));
+	/// ```
+	pub fn take_one_by<E: Equivalence<T> + ?Sized>(&mut self, target: &T, equivalence: &E) -> Option<T> {
+		self.take_first(|datum| equivalence.equivalent(datum, target))
+	}
+
+	/// Removes every element matching the target under the given equivalence and returns them.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Epsilon};
+	/// let mut x: Bag<f64> = vec![0.1 + 0.2, 0.5, 0.3].into_iter().collect();
+	/// assert_eq!(2, x.take_all_by(&0.3, &Epsilon(1e-9)).len());
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn take_all_by<E: Equivalence<T> + ?Sized>(&mut self, target: &T, equivalence: &E) -> Vec<T> {
+		self.take_matching(|datum| equivalence.equivalent(datum, target))
+	}
+}
+
+impl<T: Clone> Bag<T> {
+	/// Returns a new `Bag` holding a copy of the data, capacity and used count of `source`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// // Create an original bag.
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(3);
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// x.insert(3.0);
+	/// 
+	/// // Create the new bag from the original bag.
+	/// let y: Bag<f64> = Bag::<f64>::new_from_bag(&x);
+	/// assert_eq!(x.get_data().len(), y.get_data().len());
+	/// assert_eq!(x.size(), y.size());
+	/// ```
+	pub fn new_from_bag(source: &Bag<T>) -> Bag<T> {
+		let mut data = Vec::with_capacity(source.get_capacity() as usize);
+		data.extend_from_slice(source.get_data());
+
+		Bag::<T> {
+			data,
+			capacity: source.get_capacity(),
+			used: source.size(),
+			growth: source.growth.clone(),
+			shrink_below: source.shrink_below,
+		}
+	}
+}
+
+/// Converts a capacity into the number of elements to reserve in a `Vec`.
+/// 
+/// Fails if the capacity cannot be addressed on this platform.
+fn vec_capacity<T>(capacity: u64) -> Result<usize, BagError> {
+	let bytes = match capacity.checked_mul(mem::size_of::<T>() as u64) {
+		Some(bytes) => bytes,
+		None => return Err(BagError::CapacityOverflow),
+	};
+
+	if capacity > usize::MAX as u64 || bytes > isize::MAX as u64 {
+		return Err(BagError::CapacityOverflow);
+	}
+
+	Ok(capacity as usize)
+}
+
+impl<T> Bag<T> {
+	/// Returns a new `Bag` with a capacity of 1 and no data elements being used.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = Bag::<f64>::new();
+	/// assert_eq!(x.get_capacity(), 1);
+	/// assert_eq!(x.size(), 0);
+	/// ```
+	pub fn new() -> Bag<T> {
+		Bag::<T> {
+			data: Vec::with_capacity(1),
+			capacity: 1,
+			used: 0,
+			growth: None,
+			shrink_below: None,
+		}
+	}
+
+	/// Returns a new `Bag` with the given capacity and no data elements being used.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = Bag::<f64>::new_with_capacity(10);
+	/// assert_eq!(x.get_capacity(), 10);
+	/// assert_eq!(x.size(), 0);
+	/// ```
+	pub fn new_with_capacity(initial_capacity: u64) -> Bag<T> {
+		match Bag::<T>::try_new_with_capacity(initial_capacity) {
+			Ok(bag) => bag,
+			Err(error) => panic!("new_with_capacity() -> initial_capacity: {}", error),
+		}
+	}
+
+	/// Returns a new `Bag` with the given capacity and no data elements being used,
+	/// or a `BagError` if the capacity is zero, too large, or cannot be allocated.
+	/// 
+	/// A caller that receives `BagError::AllocationFailure` can retry with a smaller capacity.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, BagError};
+	/// let x: Bag<f64> = Bag::<f64>::try_new_with_capacity(10).unwrap();
+	/// assert_eq!(x.get_capacity(), 10);
+	/// assert_eq!(Err(BagError::ZeroCapacity), Bag::<f64>::try_new_with_capacity(0));
+	/// assert_eq!(Err(BagError::CapacityOverflow), Bag::<f64>::try_new_with_capacity(u64::MAX));
+	/// ```
+	pub fn try_new_with_capacity(initial_capacity: u64) -> Result<Bag<T>, BagError> {
+		if initial_capacity < 1 { return Err(BagError::ZeroCapacity) }
+
+		let mut data = Vec::new();
+
+		if data.try_reserve_exact(vec_capacity::<T>(initial_capacity)?).is_err() {
+			return Err(BagError::AllocationFailure);
+		}
+
+		Ok(Bag::<T> {
+			data,
+			capacity: initial_capacity,
+			used: 0,
+			growth: None,
+			shrink_below: None,
+		})
+	}
+
+	/// Returns the vector of data stored in the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// assert_eq!(1.0, x.get_data()[0]);
+	/// ```
+	pub fn get_data(&self) -> &Vec<T> {
+		&self.data
+	}
+
+	/// Returns an iterator over references to the elements of the bag.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(3.0, x.iter().sum::<f64>());
+	/// ```
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.data.iter()
+	}
+
+	/// Returns an iterator over mutable references to the elements of the bag.
+	/// 
+	/// Elements can be changed in place, but not added or removed, so `used` and `capacity` are unaffected.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// for value in x.iter_mut() {
+	///     *value *= 10.0;
+	/// }
+	/// assert_eq!(1, x.occurrences(&20.0));
+	/// ```
+	pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+		self.data.iter_mut()
+	}
+
+	/// Returns the capacity of `data` as an integer.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(1);
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(2, x.get_capacity());
+	/// ```
+	pub fn get_capacity(&self) -> u64 {
+		self.capacity
+	}
+
+	/// Returns the number of bytes of memory allocated for the elements of the bag.
+	/// 
+	/// This is the space reserved for `get_capacity()` elements; it does not include
+	/// the `Bag` struct itself or any memory the elements own.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(4);
+	/// assert_eq!(32, x.memory_footprint());
+	/// x.insert(1.0);
+	/// x.trim_to_size();
+	/// assert_eq!(8, x.memory_footprint());
+	/// ```
+	pub fn memory_footprint(&self) -> u64 {
+		(self.data.capacity() * mem::size_of::<T>()) as u64
+	}
+
+	/// Returns the number of elements being used in `data`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(5);
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn size(&self) -> u64 {
+		self.used
+	}
+
+	/// Inserts an element into the `data` list in the bag.
+	/// Checks to ensure the capacity can handle the new entry.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn insert(&mut self, entry: T) {
+		if let Err((_, error)) = self.try_insert(entry) {
+			panic!("insert -> {}", error);
+		}
+	}
+
+	/// Inserts an element into the `data` list in the bag, or returns a `BagError`
+	/// if the capacity could not be grown to hold it.
+	/// 
+	/// The bag is left unchanged when an error is returned, and the element is handed
+	/// back alongside the error so that the caller still owns it.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, BagError, FixedIncrement};
+	/// let mut x: Bag<String> = Bag::<String>::new();
+	/// assert!(x.try_insert("a".to_string()).is_ok());
+	/// assert!(x.try_insert("b".to_string()).is_ok());
+	/// assert_eq!(2, x.size());
+	/// assert_eq!(2, x.get_capacity());
+	/// 
+	/// x.set_growth_policy(FixedIncrement(u64::MAX));
+	/// assert_eq!(Err(("c".to_string(), BagError::CapacityOverflow)), x.try_insert("c".to_string()));
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn try_insert(&mut self, entry: T) -> Result<(), (T, BagError)> {
+		let required = self.size() + 1;
+
+		if let Err(error) = self.try_grow_to(required) {
+			return Err((entry, error));
+		}
+
+		self.data.push(entry);
+		self.used += 1;
+		Ok(())
+	}
+
+	/// Adds the argument to the `bag` calling the function, as `+=` does,
+	/// or returns a `BagError` if the combined capacity overflows or cannot be allocated.
+	/// 
+	/// The bag is left unchanged when an error is returned, and the addend is handed
+	/// back alongside the error so that none of its elements are lost.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, BagError, GrowthPolicy};
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(2);
+	/// let mut y: Bag<f64> = Bag::<f64>::new_with_capacity(3);
+	/// x.insert(1.0);
+	/// y.insert(2.0);
+	/// assert!(x.try_add_assign(y).is_ok());
+	/// assert_eq!(5, x.get_capacity());
+	/// assert_eq!(2, x.size());
+	/// 
+	/// #[derive(Debug)]
+	/// struct NoMerging;
+	/// 
+	/// impl GrowthPolicy for NoMerging {
+	///     fn grow(&self, current: u64, _required: u64) -> Option<u64> { current.checked_mul(2) }
+	///     fn merge(&self, _current: u64, _addend: u64, _required: u64) -> Option<u64> { None }
+	/// }
+	/// 
+	/// x.set_growth_policy(NoMerging);
+	/// let z: Bag<f64> = vec![3.0].into_iter().collect();
+	/// let (z, error) = x.try_add_assign(z).unwrap_err();
+	/// assert_eq!(BagError::CapacityOverflow, error);
+	/// assert_eq!(1, z.occurrences(&3.0));
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn try_add_assign(&mut self, mut addend: Bag<T>) -> Result<(), (Bag<T>, BagError)> {
+		let required = self.size() + addend.size();
+		let new_capacity = match self.growth_policy().merge(self.capacity, addend.get_capacity(), required) {
+			Some(merged) if merged >= required => merged,
+			Some(_) => required,
+			None => return Err((addend, BagError::CapacityOverflow)),
+		};
+
+		let additional = match vec_capacity::<T>(new_capacity) {
+			Ok(reserved) => reserved - self.data.len(),
+			Err(error) => return Err((addend, error)),
+		};
+
+		if self.data.try_reserve_exact(additional).is_err() {
+			return Err((addend, BagError::AllocationFailure));
+		}
+
+		self.data.append(&mut addend.data);
+		self.capacity = new_capacity;
+		self.used += addend.size();
+		Ok(())
+	}
+
+	/// Determines whether a new_capacity is safe to use.
+	/// If the new_capacity is safe to use, then the `data` vector
+	/// is grown in place to hold it, without copying any element.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(5.0);
+	/// let new_capacity = x.get_capacity() + 1;
+	/// x.ensure_capacity(new_capacity);
+	/// assert_eq!(2, x.get_capacity());
+	/// ```
+	pub fn ensure_capacity(&mut self, new_capacity: u64) {
+		if let Err(error) = self.try_ensure_capacity(new_capacity) {
+			panic!("ensure_capacity -> new_capacity: {}", error);
+		}
+	}
+
+	/// Grows the bag to `new_capacity` as `ensure_capacity` does, or returns a `BagError`
+	/// if the capacity is zero, too large, or cannot be allocated.
+	/// 
+	/// The bag is left unchanged when an error is returned.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, BagError};
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// assert_eq!(Ok(()), x.try_ensure_capacity(8));
+	/// assert_eq!(8, x.get_capacity());
+	/// assert_eq!(Err(BagError::ZeroCapacity), x.try_ensure_capacity(0));
+	/// assert_eq!(Err(BagError::CapacityOverflow), x.try_ensure_capacity(u64::MAX));
+	/// assert_eq!(8, x.get_capacity());
+	/// ```
+	pub fn try_ensure_capacity(&mut self, new_capacity: u64) -> Result<(), BagError> {
+		if new_capacity < 1 { return Err(BagError::ZeroCapacity) }
+
+		if self.capacity < new_capacity {
+			let additional = vec_capacity::<T>(new_capacity)? - self.data.len();
+
+			if self.data.try_reserve_exact(additional).is_err() {
+				return Err(BagError::AllocationFailure);
+			}
+
+			self.capacity = new_capacity;
+		}
+
+		Ok(())
+	}
+
+	/// Truncates the capacity of the `bag` so that `capacity` matches `used`,
+	/// releasing the memory that is no longer needed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(4);
+	/// x.insert(1.0);
+	/// assert_eq!(4, x.get_capacity());
+	/// assert_eq!(1, x.size());
+	/// x.trim_to_size();
+	/// assert_eq!(1, x.get_capacity());
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn trim_to_size(&mut self) {
+		if self.size() < self.get_capacity() {
+			let new_capacity = if self.size() <= 1 {
+				1
+			}
+			else {
+				self.size()
+			};
+
+			self.data.shrink_to(new_capacity as usize);
+			self.capacity = new_capacity;
+		}
+	}
+
+	/// Removes every element for which the predicate returns true and returns them in a new `bag`.
+	/// 
+	/// This makes a single pass over the bag, so it runs in O(n) time.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.insert(1.0);
+	/// x.insert(12.0);
+	/// x.insert(15.0);
+	/// let large: Bag<f64> = x.take_where(|value| *value > 10.0);
+	/// assert_eq!(2, large.size());
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn take_where<F: FnMut(&T) -> bool>(&mut self, predicate: F) -> Bag<T> {
+		self.take_matching(predicate).into_iter().collect()
+	}
+
+	/// Returns the number of elements for which the predicate returns true.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, 11.0, 25.0].into_iter().collect();
+	/// assert_eq!(2, x.count_if(|value| *value > 10.0));
+	/// ```
+	pub fn count_if<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> u64 {
+		let mut answer = 0;
+
+		for datum in &self.data {
+			if predicate(datum) {
+				answer += 1;
+			}
+		}
+
+		answer
+	}
+
+	/// Returns true if the predicate returns true for at least one element.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, -1.0].into_iter().collect();
+	/// assert!(x.any(|value| *value < 0.0));
+	/// ```
+	pub fn any<F: FnMut(&T) -> bool>(&self, predicate: F) -> bool {
+		self.data.iter().any(predicate)
+	}
+
+	/// Returns true if the predicate returns true for every element, including when the bag is empty.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, 1.0].into_iter().collect();
+	/// assert!(x.all(|value| *value > 0.0));
+	/// ```
+	pub fn all<F: FnMut(&T) -> bool>(&self, predicate: F) -> bool {
+		self.data.iter().all(predicate)
+	}
+
+	/// Returns a reference to an element for which the predicate returns true, or `None` if there is none.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, 11.0].into_iter().collect();
+	/// assert_eq!(Some(&11.0), x.find(|value| *value > 10.0));
+	/// assert_eq!(None, x.find(|value| *value > 20.0));
+	/// ```
+	pub fn find<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<&T> {
+		self.data.iter().find(|datum| predicate(datum))
+	}
+
+	/// Keeps only the elements for which the predicate returns true.
+	/// 
+	/// The capacity is unchanged unless a shrink threshold has been set.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = vec![4.0, -1.0, -3.0].into_iter().collect();
+	/// x.retain(|value| *value >= 0.0);
+	/// assert_eq!(1, x.size());
+	/// assert_eq!(4, x.get_capacity());
+	/// ```
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, predicate: F) {
+		self.data.retain(predicate);
+		self.used = self.data.len() as u64;
+		self.shrink_if_sparse();
+	}
+
+	/// Removes every element for which the predicate returns true and returns an iterator over them.
+	/// 
+	/// The elements are removed as soon as this is called, whether or not the iterator is consumed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = vec![4.0, -1.0, -3.0].into_iter().collect();
+	/// let negatives: f64 = x.extract_if(|value| *value < 0.0).sum();
+	/// assert_eq!(-4.0, negatives);
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn extract_if<F: FnMut(&T) -> bool>(&mut self, predicate: F) -> vec::IntoIter<T> {
+		self.take_matching(predicate).into_iter()
+	}
+
+	/// Removes every element from the bag and returns an iterator over them.
+	/// 
+	/// The bag keeps its capacity, so it can be refilled without growing again.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = vec![1.0, 2.0, 3.0].into_iter().collect();
+	/// let total: f64 = x.drain().sum();
+	/// assert_eq!(6.0, total);
+	/// assert_eq!(0, x.size());
+	/// assert_eq!(4, x.get_capacity());
+	/// ```
+	pub fn drain(&mut self) -> vec::Drain<'_, T> {
+		self.used = 0;
+		self.data.drain(..)
+	}
+
+	/// Consumes the bag and splits it into a `bag` of the elements for which the predicate
+	/// returns true and a `bag` of the rest.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![4.0, -1.0, -3.0].into_iter().collect();
+	/// let (negatives, others) = x.partition(|value| *value < 0.0);
+	/// assert_eq!(2, negatives.size());
+	/// assert_eq!(1, others.size());
+	/// ```
+	pub fn partition<F: FnMut(&T) -> bool>(mut self, predicate: F) -> (Bag<T>, Bag<T>) {
+		let matching = self.take_where(predicate);
+
+		(matching, self)
+	}
+
+	/// Sets the policy used to choose a new capacity when the bag is full or is combined with `+=`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, FixedIncrement};
+	/// let mut x: Bag<f64> = Bag::<f64>::new();
+	/// x.set_growth_policy(FixedIncrement(10));
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// assert_eq!(11, x.get_capacity());
+	/// ```
+	pub fn set_growth_policy<P: GrowthPolicy + 'static>(&mut self, policy: P) {
+		self.growth = Some(Arc::new(policy));
+	}
+
+	/// Sets the fraction of the capacity below which erasing elements makes the bag
+	/// trim itself to size, or turns automatic shrinking off when given `None`.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics if the fraction is not greater than 0 and at most 1.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let mut x: Bag<f64> = Bag::<f64>::new_with_capacity(8);
+	/// x.set_shrink_threshold(Some(0.25));
+	/// x.insert(1.0);
+	/// x.insert(2.0);
+	/// x.insert(3.0);
+	/// x.erase_one(&3.0);
+	/// assert_eq!(8, x.get_capacity());
+	/// x.erase_one(&2.0);
+	/// assert_eq!(1, x.get_capacity());
+	/// ```
+	pub fn set_shrink_threshold(&mut self, fraction: Option<f64>) {
+		if let Some(fraction) = fraction {
+			if !(fraction > 0.0 && fraction <= 1.0) {
+				panic!("set_shrink_threshold -> fraction must be > 0 and <= 1");
+			}
+		}
+
+		self.shrink_below = fraction;
+	}
+
+	/// Returns the growth policy of the bag, which is `Doubling` unless another has been set.
+	fn growth_policy(&self) -> &dyn GrowthPolicy {
+		match self.growth {
+			Some(ref policy) => &**policy,
+			None => &Doubling,
+		}
+	}
+
+	/// Asks the growth policy for a new capacity if the bag cannot hold `required` elements.
+	fn try_grow_to(&mut self, required: u64) -> Result<(), BagError> {
+		let current_capacity = self.get_capacity();
+
+		if required > current_capacity {
+			let new_capacity = match self.growth_policy().grow(current_capacity, required) {
+				Some(grown) if grown >= required => grown,
+				Some(_) => required,
+				None => return Err(BagError::CapacityOverflow),
+			};
+
+			self.try_ensure_capacity(new_capacity)?;
+		}
+
+		Ok(())
+	}
+
+	/// Erases up to `n` elements for which the predicate returns true in a single
+	/// compaction pass and returns the number erased.
+	fn erase_matching<F: FnMut(&T) -> bool>(&mut self, mut predicate: F, n: u64) -> u64 {
+		let mut number_removed = 0;
+
+		self.data.retain(|datum| {
+			if number_removed < n && predicate(datum) {
+				number_removed += 1;
+				false
+			}
+			else {
+				true
+			}
+		});
+
+		self.used -= number_removed;
+
+		if number_removed > 0 { self.shrink_if_sparse() }
+
+		number_removed
+	}
+
+	/// Removes the first element for which the predicate returns true by moving the last
+	/// element into its slot, and returns it.
+	fn take_first<F: FnMut(&T) -> bool>(&mut self, predicate: F) -> Option<T> {
+		let index = self.data.iter().position(predicate)?;
+
+		self.used -= 1;
+		let taken = self.data.swap_remove(index);
+		self.shrink_if_sparse();
+		Some(taken)
+	}
+
+	/// Removes every element for which the predicate returns true and returns them.
+	fn take_matching<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) -> Vec<T> {
+		let mut taken = Vec::new();
+		let mut index = 0;
+
+		while index < self.data.len() {
+			if predicate(&self.data[index]) {
+				taken.push(self.data.swap_remove(index));
+			}
+			else {
+				index += 1;
+			}
+		}
+
+		self.used -= taken.len() as u64;
+
+		if !taken.is_empty() { self.shrink_if_sparse() }
+
+		taken
+	}
+
+	/// Trims the bag to size if `used` has dropped below the shrink threshold.
+	fn shrink_if_sparse(&mut self) {
+		if let Some(fraction) = self.shrink_below {
+			if (self.used as f64) < fraction * self.capacity as f64 {
+				self.trim_to_size();
+			}
+		}
+	}
+}
@@ -1,15 +1,16 @@
 //! This is documentation for the `bag` crate.
 //!
 //! The bag crate is an abstract data type implementation for the bag data structure.
+extern crate rust_adt;
+
 use std::io;
 
-mod bag;
 mod bag_exam;
 mod bag_test;
 
 const MANY_TESTS: usize = 5;
 const POINTS: [i32; MANY_TESTS + 1] = [100, 32, 12, 12, 32, 12];
-const DESCRIPTION: &'static [&'static str] = &[
+const DESCRIPTION: &[&str] = &[
 	"Tests for the Bag Class",
     "Testing insert and the constant member functions",
     "Testing the copy constructor and == methodr",
@@ -23,12 +24,9 @@ pub fn read_user_input_char() -> char {
 	
 	match io::stdin().read_line(&mut user_input) {
 		Ok(_) => {
-			match user_input.chars().next() {
-				Some(c) => { return c; }
-				None => { return '*'; }
-			}
+			user_input.chars().next().unwrap_or('*')
 		}
-		Err(_) => { return '*';}
+		Err(_) => { '*' }
 	}
 }
 
@@ -0,0 +1,402 @@
+//! # OrderedBag
+//! 
+//! A bag that keeps its elements sorted, represented by two basic structures.
+//! 
+//!   1. `counts` is a BTreeMap from each distinct value to the number of times it occurs.
+//!   2. `used` is an integer that determines how many elements the bag holds in total.
+//! 
+//! Keeping the distinct values in order makes minimum, maximum, range and rank queries
+//! possible without copying and sorting the contents of a `Bag`.
+use std::borrow::Borrow;
+use std::collections::btree_map::{self, BTreeMap};
+use std::iter::{self, Flatten};
+use std::option;
+use std::ops::Bound::{Excluded, Included};
+use std::ops::RangeBounds;
+
+use Bag;
+
+/// The struct for the `OrderedBag` data type.
+/// 
+/// This struct derives the Default, Clone, Debug, PartialEq, Eq and Hash traits.
+/// Two ordered bags are equal when they hold the same elements with the same multiplicities.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OrderedBag<T: Ord> {
+	counts: BTreeMap<T, u64>,
+	used: u64,
+}
+
+impl<T: Ord> From<Bag<T>> for OrderedBag<T> {
+	/// Builds an `ordered bag` holding the same elements as the `bag`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, OrderedBag};
+	/// let mut x: Bag<u32> = Bag::<u32>::new();
+	/// x.insert(7);
+	/// x.insert(3);
+	/// let y: OrderedBag<u32> = OrderedBag::from(x);
+	/// assert_eq!(Some(&3), y.min());
+	/// ```
+	fn from(source: Bag<T>) -> OrderedBag<T> {
+		let mut bag = OrderedBag::<T>::new();
+
+		for entry in source {
+			bag.insert(entry);
+		}
+
+		bag
+	}
+}
+
+impl<T: Ord + Clone> From<OrderedBag<T>> for Bag<T> {
+	/// Builds a `bag` holding every copy of every element of the `ordered bag`, in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, OrderedBag};
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(7);
+	/// x.insert(3);
+	/// x.insert(7);
+	/// let y: Bag<u32> = Bag::from(x);
+	/// assert_eq!(&[3, 7, 7], &y.get_data()[..]);
+	/// ```
+	fn from(source: OrderedBag<T>) -> Bag<T> {
+		let mut bag = Bag::<T>::new();
+
+		for (value, count) in source.counts {
+			for _ in 1..count {
+				bag.insert(value.clone());
+			}
+
+			bag.insert(value);
+		}
+
+		bag
+	}
+}
+
+impl<T: Ord + Clone> OrderedBag<T> {
+	/// Removes and returns one copy of the smallest element, or `None` if the bag is empty.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(4);
+	/// x.insert(2);
+	/// x.insert(2);
+	/// assert_eq!(Some(2), x.pop_min());
+	/// assert_eq!(Some(2), x.pop_min());
+	/// assert_eq!(Some(4), x.pop_min());
+	/// assert_eq!(None, x.pop_min());
+	/// ```
+	pub fn pop_min(&mut self) -> Option<T> {
+		let target = match self.counts.keys().next() {
+			Some(value) => value.clone(),
+			None => return None,
+		};
+
+		self.erase_one(&target);
+		Some(target)
+	}
+
+	/// Removes and returns one copy of the largest element, or `None` if the bag is empty.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(4);
+	/// x.insert(2);
+	/// assert_eq!(Some(4), x.pop_max());
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn pop_max(&mut self) -> Option<T> {
+		let target = match self.counts.keys().next_back() {
+			Some(value) => value.clone(),
+			None => return None,
+		};
+
+		self.erase_one(&target);
+		Some(target)
+	}
+}
+
+impl<T: Ord> OrderedBag<T> {
+	/// Returns a new, empty `OrderedBag`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// assert_eq!(0, x.size());
+	/// ```
+	pub fn new() -> OrderedBag<T> {
+		OrderedBag::<T> {
+			counts: BTreeMap::new(),
+			used: 0,
+		}
+	}
+
+	/// Returns the map from each distinct element to its number of occurrences.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// assert_eq!(Some(&2), x.get_counts().get(&1));
+	/// ```
+	pub fn get_counts(&self) -> &BTreeMap<T, u64> {
+		&self.counts
+	}
+
+	/// Returns the total number of elements in the bag, counting duplicates.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn size(&self) -> u64 {
+		self.used
+	}
+
+	/// Inserts an element into the bag in logarithmic time.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(2, x.size());
+	/// ```
+	pub fn insert(&mut self, entry: T) {
+		*self.counts.entry(entry).or_insert(0) += 1;
+		self.used += 1;
+	}
+
+	/// Determines the number of occurrences of the target element in logarithmic time.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(15);
+	/// x.insert(5);
+	/// x.insert(15);
+	/// assert_eq!(2, x.occurrences(&15));
+	/// ```
+	pub fn occurrences<Q: ?Sized + Ord>(&self, target: &Q) -> u64 where T: Borrow<Q> {
+		match self.counts.get(target) {
+			Some(count) => *count,
+			None => 0,
+		}
+	}
+
+	/// Returns the number of erased elements matching the given target.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(2, x.erase(&1));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase<Q: ?Sized + Ord>(&mut self, target: &Q) -> u64 where T: Borrow<Q> {
+		match self.counts.remove(target) {
+			Some(count) => {
+				self.used -= count;
+				count
+			}
+			None => 0,
+		}
+	}
+
+	/// Returns a boolean indicating whether or not the target was found and erased.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(2);
+	/// assert_eq!(true, x.erase_one(&1));
+	/// assert_eq!(false, x.erase_one(&5));
+	/// assert_eq!(1, x.size());
+	/// ```
+	pub fn erase_one<Q: ?Sized + Ord>(&mut self, target: &Q) -> bool where T: Borrow<Q> {
+		let remaining = match self.counts.get_mut(target) {
+			Some(count) => {
+				*count -= 1;
+				*count
+			}
+			None => return false,
+		};
+
+		if remaining == 0 {
+			self.counts.remove(target);
+		}
+
+		self.used -= 1;
+		true
+	}
+
+	/// Returns the smallest element, or `None` if the bag is empty.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// assert_eq!(None, x.min());
+	/// x.insert(4);
+	/// x.insert(2);
+	/// assert_eq!(Some(&2), x.min());
+	/// ```
+	pub fn min(&self) -> Option<&T> {
+		self.counts.keys().next()
+	}
+
+	/// Returns the largest element, or `None` if the bag is empty.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(4);
+	/// x.insert(2);
+	/// assert_eq!(Some(&4), x.max());
+	/// ```
+	pub fn max(&self) -> Option<&T> {
+		self.counts.keys().next_back()
+	}
+
+	/// Returns the number of elements, counting duplicates, that fall within the range.
+	/// 
+	/// Runs in time proportional to the number of distinct values inside the range.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(5);
+	/// x.insert(5);
+	/// x.insert(9);
+	/// assert_eq!(3, x.range_count(1..9));
+	/// assert_eq!(4, x.range_count(..));
+	/// assert_eq!(0, x.range_count(9..1));
+	/// ```
+	pub fn range_count<R: RangeBounds<T>>(&self, range: R) -> u64 {
+		self.runs_in(range).map(|(_, count)| *count).sum()
+	}
+
+	/// Returns an ascending iterator over the elements within the range, repeating each
+	/// element as many times as it occurs.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(9);
+	/// x.insert(5);
+	/// x.insert(1);
+	/// x.insert(5);
+	/// let values: Vec<&u32> = x.range_iter(2..).collect();
+	/// assert_eq!(vec![&5, &5, &9], values);
+	/// 
+	/// use std::ops::Bound::Excluded;
+	/// assert_eq!(0, x.range_iter((Excluded(5), Excluded(5))).count());
+	/// ```
+	pub fn range_iter<'a, R: RangeBounds<T>>(&'a self, range: R) -> impl Iterator<Item = &'a T> + 'a {
+		self.runs_in(range).flat_map(|(value, count)| iter::repeat_n(value, *count as usize))
+	}
+
+	/// Returns the distinct values within the range and their counts, or nothing if the
+	/// range is empty or reversed, where `BTreeMap::range` would panic.
+	fn runs_in<R: RangeBounds<T>>(&self, range: R) -> Flatten<option::IntoIter<btree_map::Range<'_, T, u64>>> {
+		let reversed = match (range.start_bound(), range.end_bound()) {
+			(Included(start), Included(end)) => start > end,
+			(Included(start), Excluded(end)) | (Excluded(start), Included(end)) => start > end,
+			(Excluded(start), Excluded(end)) => start >= end,
+			_ => false,
+		};
+		let runs = if reversed { None } else { Some(self.counts.range(range)) };
+
+		runs.into_iter().flatten()
+	}
+
+	/// Returns the element at zero-based position `k` in ascending order, counting duplicates,
+	/// or `None` if `k` is not less than `size()`.
+	/// 
+	/// Runs in time proportional to the number of distinct values up to the answer.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(9);
+	/// x.insert(5);
+	/// x.insert(5);
+	/// assert_eq!(Some(&5), x.nth_smallest(0));
+	/// assert_eq!(Some(&5), x.nth_smallest(1));
+	/// assert_eq!(Some(&9), x.nth_smallest(2));
+	/// assert_eq!(None, x.nth_smallest(3));
+	/// ```
+	pub fn nth_smallest(&self, k: u64) -> Option<&T> {
+		let mut remaining = k;
+
+		for (value, count) in &self.counts {
+			if remaining < *count { return Some(value) }
+
+			remaining -= *count;
+		}
+
+		None
+	}
+
+	/// Returns the number of elements, counting duplicates, that are strictly smaller than `target`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::OrderedBag;
+	/// let mut x: OrderedBag<u32> = OrderedBag::<u32>::new();
+	/// x.insert(1);
+	/// x.insert(5);
+	/// x.insert(5);
+	/// x.insert(9);
+	/// assert_eq!(0, x.rank(&1));
+	/// assert_eq!(1, x.rank(&5));
+	/// assert_eq!(3, x.rank(&6));
+	/// ```
+	pub fn rank(&self, target: &T) -> u64 {
+		self.counts.range(..target).map(|(_, count)| *count).sum()
+	}
+}
@@ -0,0 +1,342 @@
+//! Reading a `Bag` back from text.
+//! 
+//! `str::parse` accepts two layouts. The first is the one `Display` writes, so that
+//! parsing the displayed form of a bag gives back an equal bag with the same capacity:
+//! 
+//! ```text
+//! Bag
+//! data: 1.5, 2, 1.5
+//! capacity: 4
+//! used: 3
+//! ```
+//! 
+//! The `used` line must match the number of elements, and the capacity must be at least
+//! that number and at least 1. The second layout lists the elements between braces, with
+//! an optional ` xN` suffix for an element that occurs N times, where N is at least 1:
+//! 
+//! ```text
+//! {1.5 x2, 2}
+//! ```
+//! 
+//! A bag parsed from this layout has exactly the capacity it needs. In both layouts the
+//! elements are separated by commas, surrounding whitespace is ignored, and each element
+//! is parsed with its own `FromStr` implementation. Errors report the line and column at
+//! which parsing failed.
+//! 
+//! Neither layout may describe more than `MAX_TOTAL_COUNT` elements, counting multiplicities,
+//! nor a capacity above it, so a larger bag must be parsed back with `Bag::parse_with_limit`.
+//! 
+//! Parsing the displayed form of a bag gives back an equal bag unless the displayed text
+//! of one of its elements:
+//! 
+//!   1. is empty, such as an empty `String`,
+//!   2. begins or ends with whitespace, which is trimmed,
+//!   3. contains a comma or a line break, which split it,
+//!   4. ends with whitespace, `x` and digits, which read as a multiplicity in the braced layout,
+//!   5. or is not accepted by the element type's `FromStr`.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use {Bag, MAX_TOTAL_COUNT};
+
+/// The error returned when text cannot be parsed into a `Bag`.
+/// 
+/// Lines and columns are counted from 1, and columns count characters rather than bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBagError {
+	line: usize,
+	column: usize,
+	message: String,
+}
+
+impl ParseBagError {
+	/// Returns the line at which parsing failed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let error = "Bag\ndata: 1, x\ncapacity: 2\nused: 2".parse::<Bag<u32>>().unwrap_err();
+	/// assert_eq!(2, error.line());
+	/// ```
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Returns the column at which parsing failed.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let error = "Bag\ndata: 1, x\ncapacity: 2\nused: 2".parse::<Bag<u32>>().unwrap_err();
+	/// assert_eq!(10, error.column());
+	/// ```
+	pub fn column(&self) -> usize {
+		self.column
+	}
+}
+
+impl fmt::Display for ParseBagError {
+	/// Displays the position of the error followed by a description of it.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let error = "{1, 2".parse::<Bag<u32>>().unwrap_err();
+	/// assert_eq!("line 1, column 6: expected `}`", format!("{}", error));
+	/// ```
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+	}
+}
+
+impl Error for ParseBagError {}
+
+impl<T: FromStr + Clone> FromStr for Bag<T> where T::Err: fmt::Display {
+	type Err = ParseBagError;
+
+	/// Parses a `bag` from either the `Display` layout or the braced layout.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<f64> = vec![1.5, 2.0, 1.5].into_iter().collect();
+	/// let y: Bag<f64> = format!("{}", x).parse().unwrap();
+	/// assert_eq!(x, y);
+	/// assert_eq!(x.get_capacity(), y.get_capacity());
+	/// 
+	/// let z: Bag<f64> = "{1.5 x2, 2}".parse().unwrap();
+	/// assert_eq!(x, z);
+	/// 
+	/// let w: Bag<u32> = "{1\u{a0}x2,\u{3000}3}".parse().unwrap();
+	/// assert_eq!(2, w.occurrences(&1));
+	/// assert_eq!(1, w.occurrences(&3));
+	/// 
+	/// let empty: Bag<u32> = Bag::<u32>::default();
+	/// assert_eq!(empty, format!("{}", empty).parse().unwrap());
+	/// 
+	/// let error = "{7 x3000000000}".parse::<Bag<u8>>().unwrap_err();
+	/// assert_eq!("line 1, column 5: total count exceeds 16777216", format!("{}", error));
+	/// ```
+	fn from_str(text: &str) -> Result<Bag<T>, ParseBagError> {
+		Bag::<T>::parse_with_limit(text, MAX_TOTAL_COUNT)
+	}
+}
+
+impl<T: FromStr + Clone> Bag<T> where T::Err: fmt::Display {
+	/// Parses a `bag` as `str::parse` does, but rejects text describing more than
+	/// `max_total_count` elements or a larger capacity, instead of `MAX_TOTAL_COUNT`.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::Bag;
+	/// let x: Bag<u8> = Bag::<u8>::parse_with_limit("{7 x3, 8}", 4).unwrap();
+	/// assert_eq!(4, x.size());
+	/// assert!(Bag::<u8>::parse_with_limit("{7 x3, 8 x2}", 4).is_err());
+	/// 
+	/// let error = Bag::<u8>::parse_with_limit("Bag\ndata: 7\ncapacity: 10\nused: 1", 4).unwrap_err();
+	/// assert_eq!((3, 11), (error.line(), error.column()));
+	/// ```
+	pub fn parse_with_limit(text: &str, max_total_count: u64) -> Result<Bag<T>, ParseBagError> {
+		let start = text.len() - text.trim_start().len();
+
+		if text[start..].starts_with('{') {
+			parse_braced(text, start, max_total_count)
+		}
+		else if text[start..].starts_with("Bag") {
+			parse_display(text, start, max_total_count)
+		}
+		else {
+			Err(error_at(text, start, "expected `{` or `Bag`".to_string()))
+		}
+	}
+}
+
+/// Parses the braced layout, whose opening brace is at byte `start` of `text`.
+fn parse_braced<T: FromStr + Clone>(text: &str, start: usize, max_total_count: u64) -> Result<Bag<T>, ParseBagError> where T::Err: fmt::Display {
+	let end = text.trim_end().len();
+
+	if !text[..end].ends_with('}') {
+		return Err(error_at(text, end, "expected `}`".to_string()));
+	}
+
+	let mut entries = Vec::new();
+	let mut total: u64 = 0;
+
+	for (offset, item) in items(text, start + 1, end - 1) {
+		let (element, count_offset, count) = match split_multiplicity(item) {
+			Some((element, count_offset, count_text)) => match count_text.parse::<u64>() {
+				Ok(count) if count > 0 => (element, count_offset, count),
+				_ => return Err(error_at(text, offset + count_offset, format!("invalid multiplicity `{}`", count_text))),
+			},
+			None => (item, 0, 1),
+		};
+
+		let value = parse_element(text, offset, element)?;
+
+		total = match total.checked_add(count) {
+			Some(total) if total <= max_total_count => total,
+			_ => return Err(error_at(text, offset + count_offset, format!("total count exceeds {}", max_total_count))),
+		};
+
+		entries.push((value, count));
+	}
+
+	let mut bag = match Bag::<T>::try_new_with_capacity(if total < 1 { 1 } else { total }) {
+		Ok(bag) => bag,
+		Err(error) => return Err(error_at(text, start, error.to_string())),
+	};
+
+	for (value, count) in entries {
+		bag.insert_n(value, count);
+	}
+
+	Ok(bag)
+}
+
+/// Parses the `Display` layout, whose `Bag` heading is at byte `start` of `text`.
+fn parse_display<T: FromStr>(text: &str, start: usize, max_total_count: u64) -> Result<Bag<T>, ParseBagError> where T::Err: fmt::Display {
+	let mut lines = Vec::new();
+	let mut offset = 0;
+
+	for line in text.split('\n') {
+		if offset + line.len() >= start && !line.trim().is_empty() {
+			lines.push((offset, line.trim_end()));
+		}
+
+		offset += line.len() + 1;
+	}
+
+	let (heading_offset, heading) = lines[0];
+
+	if heading.trim_start() != "Bag" {
+		return Err(error_at(text, heading_offset + heading.len() - heading.trim_start().len() + 3, "expected the end of the line after `Bag`".to_string()));
+	}
+
+	let (data_offset, data) = field(text, &lines, 1, "data")?;
+	let (capacity_offset, capacity) = field(text, &lines, 2, "capacity")?;
+	let (used_offset, used) = field(text, &lines, 3, "used")?;
+
+	if let Some(&(extra_offset, _)) = lines.get(4) {
+		return Err(error_at(text, extra_offset, "unexpected text after the `used` line".to_string()));
+	}
+
+	let mut values = Vec::new();
+
+	if !data.is_empty() {
+		for (offset, item) in items(text, data_offset, data_offset + data.len()) {
+			values.push(parse_element(text, offset, item)?);
+		}
+	}
+
+	let used = match used.parse::<u64>() {
+		Ok(used) if used == values.len() as u64 && used > max_total_count => {
+			return Err(error_at(text, used_offset, format!("total count exceeds {}", max_total_count)));
+		}
+		Ok(used) if used == values.len() as u64 => used,
+		Ok(used) => return Err(error_at(text, used_offset, format!("used is {} but data holds {} elements", used, values.len()))),
+		Err(_) => return Err(error_at(text, used_offset, format!("invalid count `{}`", used))),
+	};
+
+	let capacity = match capacity.parse::<u64>() {
+		Ok(capacity) if capacity > max_total_count => {
+			return Err(error_at(text, capacity_offset, format!("capacity exceeds {}", max_total_count)));
+		}
+		Ok(capacity) if capacity >= used => capacity,
+		Ok(capacity) => return Err(error_at(text, capacity_offset, format!("capacity {} is smaller than used {}", capacity, used))),
+		Err(_) => return Err(error_at(text, capacity_offset, format!("invalid capacity `{}`", capacity))),
+	};
+
+	let mut bag = match Bag::<T>::try_new_with_capacity(capacity) {
+		Ok(bag) => bag,
+		Err(error) => return Err(error_at(text, capacity_offset, error.to_string())),
+	};
+
+	bag.extend(values);
+
+	Ok(bag)
+}
+
+/// Returns the byte offset and trimmed value of the line at `index`, which must read `name: value`.
+fn field<'a>(text: &str, lines: &[(usize, &'a str)], index: usize, name: &str) -> Result<(usize, &'a str), ParseBagError> {
+	let (offset, line) = match lines.get(index) {
+		Some(&line) => line,
+		None => return Err(error_at(text, text.trim_end().len(), format!("expected a `{}:` line", name))),
+	};
+
+	let indent = line.len() - line.trim_start().len();
+	let rest = &line[indent..];
+
+	if !rest.starts_with(name) || !rest[name.len()..].starts_with(':') {
+		return Err(error_at(text, offset + indent, format!("expected `{}:`", name)));
+	}
+
+	let value_start = indent + name.len() + 1;
+	let value = &line[value_start..];
+	let leading = value.len() - value.trim_start().len();
+
+	Ok((offset + value_start + leading, value.trim()))
+}
+
+/// Splits the bytes of `text` from `start` to `end` at commas, and returns each trimmed
+/// item with the byte offset at which it begins.
+fn items(text: &str, start: usize, end: usize) -> Vec<(usize, &str)> {
+	let mut result = Vec::new();
+	let mut offset = start;
+
+	if text[start..end].trim().is_empty() { return result }
+
+	for item in text[start..end].split(',') {
+		let leading = item.len() - item.trim_start().len();
+		result.push((offset + leading, item.trim()));
+		offset += item.len() + 1;
+	}
+
+	result
+}
+
+/// Splits an item of the form `element xN`, where N is a run of digits, into the element,
+/// the byte offset of `N` within the item, and `N`.
+fn split_multiplicity(item: &str) -> Option<(&str, usize, &str)> {
+	let (element, suffix) = item.rsplit_once(char::is_whitespace)?;
+
+	if suffix.len() > 1 && suffix.starts_with('x') && suffix[1..].bytes().all(|byte| byte.is_ascii_digit()) {
+		Some((element.trim_end(), item.len() - suffix.len() + 1, &suffix[1..]))
+	}
+	else {
+		None
+	}
+}
+
+/// Parses one element, which begins at byte `offset` of `text`.
+fn parse_element<T: FromStr>(text: &str, offset: usize, item: &str) -> Result<T, ParseBagError> where T::Err: fmt::Display {
+	if item.is_empty() {
+		return Err(error_at(text, offset, "expected an element".to_string()));
+	}
+
+	match item.parse::<T>() {
+		Ok(value) => Ok(value),
+		Err(error) => Err(error_at(text, offset, format!("invalid element `{}`: {}", item, error))),
+	}
+}
+
+/// Returns a `ParseBagError` for the position of byte `offset` in `text`.
+fn error_at(text: &str, offset: usize, message: String) -> ParseBagError {
+	let before = &text[..offset];
+	let line = before.matches('\n').count() + 1;
+	let line_start = match before.rfind('\n') {
+		Some(index) => index + 1,
+		None => 0,
+	};
+
+	ParseBagError {
+		line,
+		column: before[line_start..].chars().count() + 1,
+		message,
+	}
+}
@@ -0,0 +1,219 @@
+//! Serde support for the `Bag` type, compiled only with the `serde` feature.
+//! 
+//! A bag serializes as a plain list of its elements. The `as_counts` module offers
+//! a compact `{element: count}` form instead, for use with `#[serde(with = "...")]`.
+//! 
+//! Deserializing gives a bag whose capacity is exactly the number of elements read,
+//! or 1 if there are none, and whose growth policy and shrink threshold are the defaults.
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use Bag;
+
+impl<T: Serialize> Serialize for Bag<T> {
+	/// Serializes the bag as a list of its elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # extern crate rust_adt;
+	/// # extern crate serde_json;
+	/// # use rust_adt::Bag;
+	/// # fn main() {
+	/// let x: Bag<f64> = vec![1.5, 2.0, 1.5].into_iter().collect();
+	/// assert_eq!("[1.5,2.0,1.5]", serde_json::to_string(&x).unwrap());
+	/// # }
+	/// ```
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut seq = serializer.serialize_seq(Some(self.data.len()))?;
+
+		for datum in &self.data {
+			seq.serialize_element(datum)?;
+		}
+
+		seq.end()
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Bag<T> {
+	/// Deserializes a bag from a list of its elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # extern crate rust_adt;
+	/// # extern crate serde_json;
+	/// # use rust_adt::Bag;
+	/// # fn main() {
+	/// let x: Bag<f64> = serde_json::from_str("[1.5, 2.0, 1.5]").unwrap();
+	/// assert_eq!(2, x.occurrences(&1.5));
+	/// assert_eq!(3, x.get_capacity());
+	/// # }
+	/// ```
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Bag<T>, D::Error> {
+		deserializer.deserialize_seq(ListVisitor(PhantomData))
+	}
+}
+
+/// Builds a `Bag` from a serialized list of elements.
+struct ListVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+	type Value = Bag<T>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a list of bag elements")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Bag<T>, A::Error> {
+		let mut data = Vec::with_capacity(cautious(seq.size_hint()));
+
+		while let Some(datum) = seq.next_element()? {
+			data.push(datum);
+		}
+
+		Ok(from_vec(data))
+	}
+}
+
+/// Returns a new `Bag` holding the elements of `data`, with a capacity of exactly
+/// the number of elements, or 1 if there are none.
+fn from_vec<T>(mut data: Vec<T>) -> Bag<T> {
+	let used = data.len() as u64;
+	let capacity = if used < 1 { 1 } else { used };
+
+	data.reserve_exact(capacity as usize - data.len());
+	data.shrink_to(capacity as usize);
+
+	let mut bag = Bag::<T>::new();
+	bag.data = data;
+	bag.capacity = capacity;
+	bag.used = used;
+
+	bag
+}
+
+/// The number of entries to reserve up front for a list or map whose length the input claims,
+/// so that a false length cannot make deserializing allocate more than the data holds.
+fn cautious(hint: Option<usize>) -> usize {
+	match hint {
+		Some(length) if length < 4096 => length,
+		Some(_) => 4096,
+		None => 0,
+	}
+}
+
+/// Serializes a `Bag` as a map from each distinct element to the number of times it occurs.
+/// 
+/// Use it on a field with `#[serde(with = "rust_adt::as_counts")]`. Deserializing rejects
+/// counts of zero and elements that appear more than once, since neither could have been
+/// written by `serialize`.
+/// 
+/// A few bytes of input can ask for billions of elements, so `deserialize` also rejects maps
+/// whose counts add up to more than `MAX_TOTAL_COUNT`. Use `deserialize_with_limit` from a
+/// `#[serde(deserialize_with = "...")]` function to allow a different total.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # extern crate rust_adt;
+/// # extern crate serde_json;
+/// # use rust_adt::{as_counts, Bag};
+/// # fn main() {
+/// let x: Bag<String> = vec!["a".to_string(), "b".to_string(), "a".to_string()].into_iter().collect();
+/// let mut json = Vec::new();
+/// as_counts::serialize(&x, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// assert_eq!(r#"{"a":2,"b":1}"#, String::from_utf8(json).unwrap());
+/// 
+/// let y: Bag<String> = as_counts::deserialize(&mut serde_json::Deserializer::from_str(r#"{"a":2,"b":1}"#)).unwrap();
+/// assert_eq!(x, y);
+/// assert!(as_counts::deserialize::<_, String>(&mut serde_json::Deserializer::from_str(r#"{"a":0}"#)).is_err());
+/// assert!(as_counts::deserialize::<_, String>(&mut serde_json::Deserializer::from_str(r#"{"a":1,"a":2}"#)).is_err());
+/// assert!(as_counts::deserialize::<_, String>(&mut serde_json::Deserializer::from_str(r#"{"a":1000000000}"#)).is_err());
+/// 
+/// let z: Bag<String> = as_counts::deserialize_with_limit(&mut serde_json::Deserializer::from_str(r#"{"a":2,"b":1}"#), 3).unwrap();
+/// assert_eq!(x, z);
+/// assert!(as_counts::deserialize_with_limit::<_, String>(&mut serde_json::Deserializer::from_str(r#"{"a":2,"b":1}"#), 2).is_err());
+/// # }
+/// ```
+pub mod as_counts {
+	use std::collections::HashSet;
+	use std::fmt;
+	use std::hash::Hash;
+	use std::marker::PhantomData;
+
+	use serde::de::{Deserialize, Deserializer, Error, MapAccess, Visitor};
+	use serde::ser::{Serialize, SerializeMap, Serializer};
+
+	use {Bag, MAX_TOTAL_COUNT};
+
+	/// Serializes the bag as a map from each distinct element to its count,
+	/// in order of first appearance.
+	pub fn serialize<T: Serialize + Hash + Eq, S: Serializer>(bag: &Bag<T>, serializer: S) -> Result<S::Ok, S::Error> {
+		let counts: Vec<(&T, u64)> = bag.counts().collect();
+		let mut map = serializer.serialize_map(Some(counts.len()))?;
+
+		for (datum, count) in counts {
+			map.serialize_entry(datum, &count)?;
+		}
+
+		map.end()
+	}
+
+	/// Deserializes a bag from a map of elements to counts, rejecting maps whose counts
+	/// add up to more than `MAX_TOTAL_COUNT`.
+	pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de> + Hash + Eq + Clone>(deserializer: D) -> Result<Bag<T>, D::Error> {
+		deserialize_with_limit(deserializer, MAX_TOTAL_COUNT)
+	}
+
+	/// Deserializes a bag from a map of elements to counts, rejecting maps whose counts
+	/// add up to more than `max_total_count`.
+	pub fn deserialize_with_limit<'de, D: Deserializer<'de>, T: Deserialize<'de> + Hash + Eq + Clone>(deserializer: D, max_total_count: u64) -> Result<Bag<T>, D::Error> {
+		deserializer.deserialize_map(CountsVisitor { max_total_count, element: PhantomData })
+	}
+
+	/// Builds a `Bag` from a serialized map of elements to counts.
+	struct CountsVisitor<T> {
+		max_total_count: u64,
+		element: PhantomData<T>,
+	}
+
+	impl<'de, T: Deserialize<'de> + Hash + Eq + Clone> Visitor<'de> for CountsVisitor<T> {
+		type Value = Bag<T>;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str("a map from bag elements to their counts")
+		}
+
+		fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Bag<T>, A::Error> {
+			let mut counts: Vec<(T, u64)> = Vec::with_capacity(super::cautious(map.size_hint()));
+			let mut total: u64 = 0;
+
+			while let Some((datum, count)) = map.next_entry::<T, u64>()? {
+				if count == 0 { return Err(A::Error::custom("element has a count of zero")) }
+
+				total = match total.checked_add(count) {
+					Some(total) if total <= self.max_total_count => total,
+					_ => return Err(A::Error::custom(format!("total count exceeds {}", self.max_total_count))),
+				};
+
+				counts.push((datum, count));
+			}
+
+			if counts.iter().map(|(datum, _)| datum).collect::<HashSet<&T>>().len() != counts.len() {
+				return Err(A::Error::custom("element appears more than once"));
+			}
+
+			let mut bag = Bag::<T>::try_new_with_capacity(if total < 1 { 1 } else { total }).map_err(A::Error::custom)?;
+
+			for (datum, count) in counts {
+				bag.try_insert_n(datum, count).map_err(A::Error::custom)?;
+			}
+
+			Ok(bag)
+		}
+	}
+}
@@ -0,0 +1,367 @@
+//! Descriptive statistics for bags of numbers.
+//! 
+//! The `Statistics` trait is implemented for every `Bag<T>` whose elements are `Numeric`,
+//! which covers `Bag<f64>` as well as every other primitive integer and float type.
+//! Bring it into scope with `use rust_adt::Statistics;`.
+//! 
+//! `sum` adds the elements in their own type and returns `None` if that overflows.
+//! The other statistics are computed in `f64`, so they never overflow, although
+//! integers beyond 2^53 lose precision. `min`, `max` and `modes_by_value` compare the
+//! elements themselves and are exact for every type.
+//! 
+//! Every statistic except `sum` returns `None` for an empty bag. A NaN element makes
+//! `sum`, `mean`, `median`, `quantile`, `variance`, `std_dev`, `range` and `geometric_mean`
+//! NaN, and makes `min` and `max` return it. `modes_by_value` ignores NaN, since NaN is
+//! never equal to itself.
+use std::cmp::Ordering;
+
+use Bag;
+
+/// Element types that `Statistics` can summarise.
+pub trait Numeric: Copy + Default + PartialOrd {
+	/// Returns the value as an `f64`, rounding it if it cannot be represented exactly.
+	fn to_f64(self) -> f64;
+
+	/// Returns the sum of two values, or `None` if it cannot be represented in the type.
+	fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+	($($number:ty),*) => {$(
+		impl Numeric for $number {
+			fn to_f64(self) -> f64 {
+				self as f64
+			}
+
+			fn checked_add(self, other: $number) -> Option<$number> {
+				<$number>::checked_add(self, other)
+			}
+		}
+	)*}
+}
+
+macro_rules! float {
+	($($number:ty),*) => {$(
+		impl Numeric for $number {
+			fn to_f64(self) -> f64 {
+				self as f64
+			}
+
+			fn checked_add(self, other: $number) -> Option<$number> {
+				Some(self + other)
+			}
+		}
+	)*}
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+float!(f32, f64);
+
+/// Summary statistics over the elements of a numeric `Bag`.
+pub trait Statistics {
+	/// The element type of the bag.
+	type Item;
+
+	/// Returns the sum of the elements, or `None` if it overflows the element type.
+	/// The sum of an empty bag is zero.
+	fn sum(&self) -> Option<Self::Item>;
+
+	/// Returns the arithmetic mean of the elements.
+	fn mean(&self) -> Option<f64>;
+
+	/// Returns the middle element, or the mean of the two middle elements if the size is even.
+	fn median(&self) -> Option<f64>;
+
+	/// Returns the `p` quantile of the elements, interpolating linearly between the two
+	/// closest ranks. `quantile(0.5)` is the median.
+	/// 
+	/// # Panics
+	/// 
+	/// Panics unless `p` is between 0 and 1 inclusive.
+	fn quantile(&self, p: f64) -> Option<f64>;
+
+	/// Returns the population variance of the elements.
+	fn variance(&self) -> Option<f64>;
+
+	/// Returns the population standard deviation of the elements.
+	fn std_dev(&self) -> Option<f64>;
+
+	/// Returns the smallest element.
+	fn min(&self) -> Option<Self::Item>;
+
+	/// Returns the largest element.
+	fn max(&self) -> Option<Self::Item>;
+
+	/// Returns the difference between the largest and smallest elements.
+	fn range(&self) -> Option<f64>;
+
+	/// Returns every value that occurs as often as the most common one, in ascending order.
+	/// 
+	/// Unlike `Bag::mode`, which returns references in order of first appearance, this
+	/// returns copies of the values sorted by value.
+	fn modes_by_value(&self) -> Vec<Self::Item>;
+
+	/// Returns the geometric mean of the elements, which is NaN if any element is negative.
+	fn geometric_mean(&self) -> Option<f64>;
+}
+
+impl<T: Numeric> Statistics for Bag<T> {
+	type Item = T;
+
+	/// Returns the sum of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![1.5, 2.0, 3.5].into_iter().collect();
+	/// assert_eq!(Some(7.0), x.sum());
+	/// assert_eq!(Some(0.0), Bag::<f64>::new().sum());
+	/// 
+	/// let y: Bag<u8> = vec![200, 200].into_iter().collect();
+	/// assert_eq!(None, y.sum());
+	/// 
+	/// let z: Bag<u128> = vec![u64::MAX as u128, u64::MAX as u128].into_iter().collect();
+	/// assert_eq!(Some(2 * u64::MAX as u128), z.sum());
+	/// ```
+	fn sum(&self) -> Option<T> {
+		let mut total = T::default();
+
+		for &value in &self.data {
+			total = total.checked_add(value)?;
+		}
+
+		Some(total)
+	}
+
+	/// Returns the arithmetic mean of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<u32> = vec![1, 2, 6].into_iter().collect();
+	/// assert_eq!(Some(3.0), x.mean());
+	/// assert_eq!(None, Bag::<f64>::new().mean());
+	/// 
+	/// let y: Bag<i64> = vec![i64::MAX, i64::MAX].into_iter().collect();
+	/// assert_eq!(Some(i64::MAX as f64), y.mean());
+	/// ```
+	fn mean(&self) -> Option<f64> {
+		if self.data.is_empty() { return None }
+
+		Some(self.data.iter().map(|&value| value.to_f64()).sum::<f64>() / self.data.len() as f64)
+	}
+
+	/// Returns the median of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![4.0, 1.0, 3.0, 2.0].into_iter().collect();
+	/// assert_eq!(Some(2.5), x.median());
+	/// ```
+	fn median(&self) -> Option<f64> {
+		self.quantile(0.5)
+	}
+
+	/// Returns the `p` quantile of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![10.0, 20.0, 30.0, 40.0, 50.0].into_iter().collect();
+	/// assert_eq!(Some(20.0), x.quantile(0.25));
+	/// assert_eq!(Some(50.0), x.quantile(1.0));
+	/// assert!(vec![1.0, f64::NAN].into_iter().collect::<Bag<f64>>().quantile(0.5).unwrap().is_nan());
+	/// 
+	/// let y: Bag<f64> = vec![1.0, f64::INFINITY].into_iter().collect();
+	/// assert_eq!(Some(f64::INFINITY), y.quantile(1.0));
+	/// assert_eq!(Some(f64::INFINITY), vec![f64::INFINITY].into_iter().collect::<Bag<f64>>().median());
+	/// ```
+	fn quantile(&self, p: f64) -> Option<f64> {
+		if !(0.0..=1.0).contains(&p) {
+			panic!("quantile -> p must be between 0 and 1");
+		}
+
+		let sorted = self.sorted()?;
+
+		if sorted.iter().any(|value| value.is_nan()) { return Some(f64::NAN) }
+
+		let position = p * (sorted.len() - 1) as f64;
+		let lower = position.floor() as usize;
+		let upper = position.ceil() as usize;
+		let fraction = position - lower as f64;
+
+		if lower == upper || fraction == 0.0 { return Some(sorted[lower]) }
+
+		Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+	}
+
+	/// Returns the population variance of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].into_iter().collect();
+	/// assert_eq!(Some(4.0), x.variance());
+	/// ```
+	fn variance(&self) -> Option<f64> {
+		let mean = self.mean()?;
+		let squares: f64 = self.data.iter().map(|&value| (value.to_f64() - mean).powi(2)).sum();
+
+		Some(squares / self.data.len() as f64)
+	}
+
+	/// Returns the population standard deviation of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].into_iter().collect();
+	/// assert_eq!(Some(2.0), x.std_dev());
+	/// ```
+	fn std_dev(&self) -> Option<f64> {
+		self.variance().map(f64::sqrt)
+	}
+
+	/// Returns the smallest element.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![3.0, -1.5, 2.0].into_iter().collect();
+	/// assert_eq!(Some(-1.5), x.min());
+	/// assert_eq!(None, Bag::<f64>::new().min());
+	/// 
+	/// let y: Bag<u64> = vec![u64::MAX, u64::MAX - 1].into_iter().collect();
+	/// assert_eq!(Some(u64::MAX - 1), y.min());
+	/// ```
+	fn min(&self) -> Option<T> {
+		self.extreme(Ordering::Less)
+	}
+
+	/// Returns the largest element.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![3.0, -1.5, 2.0].into_iter().collect();
+	/// assert_eq!(Some(3.0), x.max());
+	/// ```
+	fn max(&self) -> Option<T> {
+		self.extreme(Ordering::Greater)
+	}
+
+	/// Returns the difference between the largest and smallest elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![3.0, -1.5, 2.0].into_iter().collect();
+	/// assert_eq!(Some(4.5), x.range());
+	/// ```
+	fn range(&self) -> Option<f64> {
+		let smallest = Statistics::min(self)?.to_f64();
+		let largest = Statistics::max(self)?.to_f64();
+
+		Some(largest - smallest)
+	}
+
+	/// Returns every value that occurs as often as the most common one, in ascending order.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![2.5, 1.0, 2.5, 1.0, 3.0, f64::NAN].into_iter().collect();
+	/// assert_eq!(vec![1.0, 2.5], x.modes_by_value());
+	/// ```
+	fn modes_by_value(&self) -> Vec<T> {
+		let mut values: Vec<T> = self.data.iter().cloned().filter(|value| !unordered(value)).collect();
+		values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+		let mut modes = Vec::new();
+		let mut highest = 0;
+		let mut start = 0;
+
+		while start < values.len() {
+			let mut end = start + 1;
+
+			while end < values.len() && values[end] == values[start] {
+				end += 1;
+			}
+
+			if end - start > highest {
+				highest = end - start;
+				modes.clear();
+			}
+
+			if end - start == highest {
+				modes.push(values[start]);
+			}
+
+			start = end;
+		}
+
+		modes
+	}
+
+	/// Returns the geometric mean of the elements.
+	/// 
+	/// # Examples
+	/// 
+	/// ```
+	/// # use rust_adt::{Bag, Statistics};
+	/// let x: Bag<f64> = vec![2.0, 8.0].into_iter().collect();
+	/// assert!((x.geometric_mean().unwrap() - 4.0).abs() < 1e-12);
+	/// assert!(vec![-2.0, 8.0].into_iter().collect::<Bag<f64>>().geometric_mean().unwrap().is_nan());
+	/// ```
+	fn geometric_mean(&self) -> Option<f64> {
+		if self.data.is_empty() { return None }
+
+		let logs: f64 = self.data.iter().map(|&value| value.to_f64().ln()).sum();
+
+		Some((logs / self.data.len() as f64).exp())
+	}
+}
+
+impl<T: Numeric> Bag<T> {
+	/// Returns the elements as `f64` values in ascending order, or `None` if the bag is empty.
+	fn sorted(&self) -> Option<Vec<f64>> {
+		if self.data.is_empty() { return None }
+
+		let mut sorted: Vec<f64> = self.data.iter().map(|&value| value.to_f64()).collect();
+		sorted.sort_by(f64::total_cmp);
+
+		Some(sorted)
+	}
+
+	/// Returns the element that compares as `wanted` against every other, or the first NaN.
+	fn extreme(&self, wanted: Ordering) -> Option<T> {
+		let mut best: Option<T> = None;
+
+		for &value in &self.data {
+			if unordered(&value) { return Some(value) }
+
+			best = match best {
+				Some(current) if value.partial_cmp(&current) != Some(wanted) => Some(current),
+				_ => Some(value),
+			};
+		}
+
+		best
+	}
+}
+
+/// Returns true if the value cannot be compared even with itself, as NaN cannot.
+fn unordered<T: PartialOrd>(value: &T) -> bool {
+	value.partial_cmp(value).is_none()
+}